@@ -0,0 +1,43 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use multisig_rs::{generate_keypair, MultisigWallet, Transaction};
+use secp256k1::{PublicKey, SecretKey};
+
+const SIGNERS: usize = 5;
+const TRANSACTIONS: usize = 200;
+
+/// A wallet holding many pending transactions, each signed by every signer
+fn setup() -> MultisigWallet {
+    let keys: Vec<(SecretKey, PublicKey)> = (0..SIGNERS)
+        .map(|_| generate_keypair().unwrap())
+        .collect();
+    let mut wallet = MultisigWallet::new(SIGNERS, keys.iter().map(|(_, pk)| *pk).collect()).unwrap();
+
+    for i in 0..TRANSACTIONS {
+        let tx = Transaction::new(format!("recipient-{}", i), 1000, Some("Invoice".to_string()));
+        wallet.propose_transaction(tx.clone()).unwrap();
+        for (sk, pk) in &keys {
+            let signature = tx.sign(sk, wallet.wallet_id()).unwrap();
+            wallet.add_signature(&tx.id, signature, pk).unwrap();
+        }
+    }
+    wallet
+}
+
+fn bench_encode_decode(c: &mut Criterion) {
+    let wallet = setup();
+    let json = wallet.to_json().unwrap();
+    let bincode = wallet.to_bincode().unwrap();
+    println!("wallet size: json {} bytes, bincode {} bytes", json.len(), bincode.len());
+
+    let mut group = c.benchmark_group("wallet_serialization");
+    group.bench_function("to_json", |b| b.iter(|| wallet.to_json().unwrap()));
+    group.bench_function("to_bincode", |b| b.iter(|| wallet.to_bincode().unwrap()));
+    group.bench_function("from_json", |b| b.iter(|| MultisigWallet::from_json(&json).unwrap()));
+    group.bench_function("from_bincode", |b| {
+        b.iter(|| MultisigWallet::from_bincode(&bincode).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode_decode);
+criterion_main!(benches);
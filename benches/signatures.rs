@@ -0,0 +1,91 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use multisig_rs::crypto::verify_signature_with_ctx;
+use multisig_rs::{generate_keypair, sign_message, verify_signature, MultisigWallet, Transaction};
+use secp256k1::{ecdsa::Signature, PublicKey, Secp256k1, SecretKey};
+
+const SIGNERS: usize = 20;
+
+fn setup() -> (MultisigWallet, Transaction, Vec<(Signature, PublicKey)>) {
+    let keys: Vec<(SecretKey, PublicKey)> = (0..SIGNERS)
+        .map(|_| generate_keypair().unwrap())
+        .collect();
+
+    let wallet = MultisigWallet::new(SIGNERS, keys.iter().map(|(_, pk)| *pk).collect()).unwrap();
+    let tx = Transaction::new("recipient".to_string(), 1000, None);
+    let sigs = keys
+        .iter()
+        .map(|(sk, pk)| (tx.sign(sk, wallet.wallet_id()).unwrap(), *pk))
+        .collect();
+
+    (wallet, tx, sigs)
+}
+
+fn bench_add_signatures(c: &mut Criterion) {
+    let mut group = c.benchmark_group("add_signatures");
+
+    group.bench_function("individual", |b| {
+        b.iter_batched(
+            setup,
+            |(mut wallet, tx, sigs)| {
+                wallet.propose_transaction(tx.clone()).unwrap();
+                for (sig, pk) in sigs {
+                    wallet.add_signature(&tx.id, sig, &pk).unwrap();
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("batch", |b| {
+        b.iter_batched(
+            setup,
+            |(mut wallet, tx, sigs)| {
+                wallet.propose_transaction(tx.clone()).unwrap();
+                wallet.add_signatures_batch(&tx.id, sigs).unwrap();
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn bench_verify_context(c: &mut Criterion) {
+    let (sk, pk) = generate_keypair().unwrap();
+    let message = b"verify-heavy workload";
+    let sig = sign_message(message, &sk).unwrap();
+
+    let mut group = c.benchmark_group("verify_signature");
+
+    group.bench_function("fresh_context", |b| {
+        b.iter(|| verify_signature_with_ctx(&Secp256k1::new(), message, &sig, &pk).unwrap())
+    });
+
+    group.bench_function("shared_context", |b| {
+        b.iter(|| verify_signature(message, &sig, &pk).unwrap())
+    });
+
+    group.finish();
+}
+
+fn bench_read_heavy(c: &mut Criterion) {
+    let (mut wallet, tx, sigs) = setup();
+    wallet.propose_transaction(tx.clone()).unwrap();
+    wallet.add_signatures_batch(&tx.id, sigs).unwrap();
+    let bundle = wallet.finalize(&tx.id).unwrap();
+
+    let mut group = c.benchmark_group("read_heavy");
+
+    group.bench_function("verify_bundle_cached", |b| {
+        b.iter(|| assert!(wallet.verify_signed_transaction(&bundle).unwrap()))
+    });
+
+    group.bench_function("reverify_all", |b| {
+        b.iter(|| assert!(wallet.reverify_all(&tx.id).unwrap()))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_add_signatures, bench_verify_context, bench_read_heavy);
+criterion_main!(benches);
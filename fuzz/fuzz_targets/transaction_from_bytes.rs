@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use multisig_rs::Transaction;
+
+fuzz_target!(|data: &[u8]| {
+    // Must never panic, and anything accepted must survive a round trip
+    if let Ok(tx) = Transaction::from_bytes(data) {
+        let decoded = Transaction::from_bytes(&tx.to_bytes()).expect("re-encoded transaction");
+        assert_eq!(decoded.id, tx.id);
+        assert_eq!(decoded.signing_hash(), tx.signing_hash());
+    }
+});
@@ -0,0 +1,79 @@
+use bip39::Mnemonic;
+use bitcoin::bip32::Xpriv;
+use bitcoin::NetworkKind;
+use secp256k1::{PublicKey, SecretKey};
+use crate::crypto::context;
+use crate::error::{MultisigError, Result};
+
+/// Number of words in phrases produced by `generate_mnemonic`
+const WORD_COUNT: usize = 24;
+
+/// Generate a fresh 24-word English BIP39 seed phrase
+pub fn generate_mnemonic() -> String {
+    Mnemonic::generate(WORD_COUNT)
+        .expect("24 is a valid BIP39 word count")
+        .to_string()
+}
+
+/// Restore a signer keypair from a BIP39 seed phrase
+///
+/// The key is the BIP32 master key of the phrase's seed, so it matches what
+/// `hd::master_from_seed` produces for the same seed. The returned keys work
+/// with `sign_message` and can be authorized in `MultisigWallet::new` like
+/// any key from `generate_keypair`.
+pub fn mnemonic_to_keypair(phrase: &str, passphrase: &str) -> Result<(SecretKey, PublicKey)> {
+    let mnemonic = Mnemonic::parse(phrase).map_err(|_| MultisigError::InvalidMnemonic)?;
+    let seed = mnemonic.to_seed(passphrase);
+
+    let master = Xpriv::new_master(NetworkKind::Main, &seed)
+        .map_err(|e| MultisigError::CryptoError(e.to_string()))?;
+    let secret_key = master.private_key;
+    Ok((secret_key, PublicKey::from_secret_key(context(), &secret_key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{sign_message, verify_signature};
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_mnemonic_test_vector() {
+        let (secret_key, public_key) = mnemonic_to_keypair(PHRASE, "TREZOR").unwrap();
+        assert_eq!(
+            hex::encode(secret_key.secret_bytes()),
+            "cbedc75b0d6412c85c79bc13875112ef912fd1e756631b5a00330866f22ff184"
+        );
+        assert_eq!(
+            hex::encode(public_key.serialize()),
+            "02f632717d78bf73e74aa8461e2e782532abae4eed5110241025afb59ebfd3d2fd"
+        );
+
+        // The passphrase selects a different key
+        assert_ne!(mnemonic_to_keypair(PHRASE, "").unwrap().1, public_key);
+    }
+
+    #[test]
+    fn test_generated_mnemonic_restores() {
+        let phrase = generate_mnemonic();
+        assert_eq!(phrase.split_whitespace().count(), WORD_COUNT);
+
+        let (secret_key, public_key) = mnemonic_to_keypair(&phrase, "").unwrap();
+        let signature = sign_message(b"Restored", &secret_key).unwrap();
+        assert!(verify_signature(b"Restored", &signature, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_mnemonic() {
+        let bad_checksum = PHRASE.replace("about", "abandon");
+        assert!(matches!(
+            mnemonic_to_keypair(&bad_checksum, ""),
+            Err(MultisigError::InvalidMnemonic)
+        ));
+        assert!(matches!(
+            mnemonic_to_keypair("not a real seed phrase", ""),
+            Err(MultisigError::InvalidMnemonic)
+        ));
+    }
+}
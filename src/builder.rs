@@ -0,0 +1,142 @@
+use secp256k1::PublicKey;
+use crate::crypto::{HashAlgorithm, MessageMode, SignatureScheme};
+use crate::error::{MultisigError, Result};
+use crate::wallet::MultisigWallet;
+
+/// Step-by-step construction of a `MultisigWallet` with optional features
+#[derive(Debug, Clone, Default)]
+pub struct MultisigWalletBuilder {
+    threshold: Option<usize>,
+    signers: Vec<(PublicKey, u32)>,
+    max_amount: Option<u128>,
+    default_expiry: Option<u64>,
+    scheme: SignatureScheme,
+    hash_algorithm: HashAlgorithm,
+    message_mode: MessageMode,
+}
+
+impl MultisigWalletBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the required signature threshold (or weight for weighted signers)
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Add a signer with a weight of 1
+    pub fn add_signer(self, pubkey: PublicKey) -> Self {
+        self.add_weighted_signer(pubkey, 1)
+    }
+
+    /// Add a signer carrying the given voting weight
+    pub fn add_weighted_signer(mut self, pubkey: PublicKey, weight: u32) -> Self {
+        self.signers.push((pubkey, weight));
+        self
+    }
+
+    /// Cap the amount any single transaction may move
+    pub fn max_amount(mut self, limit: u128) -> Self {
+        self.max_amount = Some(limit);
+        self
+    }
+
+    /// Expire proposals without their own expiry `ttl` seconds after proposal
+    pub fn default_expiry(mut self, ttl: u64) -> Self {
+        self.default_expiry = Some(ttl);
+        self
+    }
+
+    /// Select the signature algorithm signers must use
+    pub fn signature_scheme(mut self, scheme: SignatureScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Select the digest signers apply before signing
+    pub fn hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = algorithm;
+        self
+    }
+
+    /// Select how the signing payload is framed before hashing
+    pub fn message_mode(mut self, mode: MessageMode) -> Self {
+        self.message_mode = mode;
+        self
+    }
+
+    /// Validate the configuration and create the wallet
+    pub fn build(self) -> Result<MultisigWallet> {
+        let threshold = self.threshold.ok_or(MultisigError::InvalidThreshold {
+            m: 0,
+            n: self.signers.len(),
+        })?;
+
+        let mut wallet = MultisigWallet::new_weighted(threshold, self.signers)?
+            .with_signature_scheme(self.scheme)
+            .with_hash_algorithm(self.hash_algorithm)
+            .with_message_mode(self.message_mode);
+
+        if let Some(limit) = self.max_amount {
+            wallet = wallet.with_max_amount(limit);
+        }
+
+        if let Some(ttl) = self.default_expiry {
+            wallet = wallet.with_default_expiry(ttl);
+        }
+
+        Ok(wallet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::transaction::Transaction;
+
+    #[test]
+    fn test_builder() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::builder()
+            .threshold(2)
+            .add_weighted_signer(pk1, 2)
+            .add_signer(pk2)
+            .max_amount(1000)
+            .build()
+            .unwrap();
+
+        let info = wallet.info();
+        assert_eq!(info.threshold, 2);
+        assert_eq!(info.total_signers, 2);
+        assert_eq!(info.total_weight, 3);
+
+        let over_limit = Transaction::new("recipient".to_string(), 5000, None);
+        assert!(matches!(
+            wallet.propose_transaction(over_limit),
+            Err(MultisigError::AmountExceedsLimit { .. })
+        ));
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let (_, pk1) = generate_keypair().unwrap();
+
+        let missing_threshold = MultisigWalletBuilder::new().add_signer(pk1).build();
+        assert!(matches!(missing_threshold, Err(MultisigError::InvalidThreshold { m: 0, n: 1 })));
+
+        let unreachable = MultisigWalletBuilder::new().threshold(2).add_signer(pk1).build();
+        assert!(matches!(unreachable, Err(MultisigError::InvalidThreshold { m: 2, n: 1 })));
+
+        let zero_weight = MultisigWalletBuilder::new()
+            .threshold(1)
+            .add_weighted_signer(pk1, 0)
+            .build();
+        assert!(matches!(zero_weight, Err(MultisigError::InvalidWeight)));
+    }
+}
@@ -0,0 +1,276 @@
+use clap::{Parser, Subcommand};
+use multisig_rs::error::{MultisigError, Result};
+use multisig_rs::{generate_keypair, keypair_from_seed, MultisigWallet, Recipient, Transaction};
+use secp256k1::PublicKey;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Command-line front end for a multisig wallet stored in a JSON file
+#[derive(Debug, Parser)]
+#[command(name = "multisig-rs", version, about)]
+pub struct Cli {
+    /// Wallet state file, read and written by every command but `keygen`
+    #[arg(long, global = true, default_value = "wallet.json")]
+    pub wallet: PathBuf,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Generate a signer keypair and print it as hex
+    Keygen,
+    /// Create a new wallet file
+    CreateWallet {
+        /// Signatures required to execute
+        #[arg(long)]
+        threshold: usize,
+        /// Comma-separated hex public keys of the signers
+        #[arg(long, value_delimiter = ',', required = true)]
+        keys: Vec<String>,
+    },
+    /// Propose a transaction and print its id
+    Propose {
+        #[arg(long)]
+        amount: u128,
+        #[arg(long)]
+        recipient: Recipient,
+        #[arg(long)]
+        metadata: Option<String>,
+    },
+    /// Sign a pending transaction with a hex secret key
+    Sign {
+        #[arg(long)]
+        tx_id: String,
+        #[arg(long)]
+        key: String,
+    },
+    /// Print the status of a transaction as JSON
+    Status {
+        #[arg(long)]
+        tx_id: String,
+    },
+    /// Execute a transaction that has enough signatures
+    Execute {
+        #[arg(long)]
+        tx_id: String,
+    },
+}
+
+/// Run a parsed command, writing human-readable output to `out`
+pub fn run(cli: Cli, out: &mut dyn Write) -> Result<()> {
+    match cli.command {
+        Command::Keygen => {
+            let (secret_key, public_key) = generate_keypair()?;
+            writeln!(out, "secret: {}", hex::encode(secret_key.secret_bytes()))?;
+            writeln!(out, "public: {}", hex::encode(public_key.serialize()))?;
+        }
+        Command::CreateWallet { threshold, keys } => {
+            if cli.wallet.exists() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!("{} already exists", cli.wallet.display()),
+                )
+                .into());
+            }
+            let wallet = MultisigWallet::from_hex_keys(threshold, keys)?;
+            wallet.save_to_file(&cli.wallet)?;
+            writeln!(
+                out,
+                "created {}-of-{} wallet {}",
+                threshold,
+                wallet.info().total_signers,
+                wallet.wallet_id()
+            )?;
+        }
+        Command::Propose {
+            amount,
+            recipient,
+            metadata,
+        } => {
+            let mut wallet = MultisigWallet::load_from_file(&cli.wallet)?;
+            let tx_id = wallet.propose_with_next_nonce(recipient, amount, metadata)?;
+            wallet.save_to_file(&cli.wallet)?;
+            writeln!(out, "{}", tx_id)?;
+        }
+        Command::Sign { tx_id, key } => {
+            let mut wallet = MultisigWallet::load_from_file(&cli.wallet)?;
+            let (secret_key, public_key) = parse_secret_key(&key)?;
+            let transaction = find_pending(&wallet, &tx_id)?;
+            let signature = transaction.sign(&secret_key, wallet.wallet_id())?;
+            wallet.add_signature(&tx_id, signature, &public_key)?;
+            wallet.save_to_file(&cli.wallet)?;
+
+            let status = wallet.transaction_status(&tx_id)?;
+            writeln!(
+                out,
+                "signed ({}/{})",
+                status.signatures_collected, status.signatures_required
+            )?;
+        }
+        Command::Status { tx_id } => {
+            let wallet = MultisigWallet::load_from_file(&cli.wallet)?;
+            let status = wallet.transaction_status(&tx_id)?;
+            writeln!(out, "{}", serde_json::to_string_pretty(&status)?)?;
+        }
+        Command::Execute { tx_id } => {
+            let mut wallet = MultisigWallet::load_from_file(&cli.wallet)?;
+            let transaction = wallet.execute_transaction(&tx_id)?;
+            wallet.save_to_file(&cli.wallet)?;
+            writeln!(
+                out,
+                "executed {} ({} to {})",
+                transaction.id, transaction.amount, transaction.recipient
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_secret_key(key_hex: &str) -> Result<(secp256k1::SecretKey, PublicKey)> {
+    let seed: [u8; 32] = hex::decode(key_hex.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(MultisigError::InvalidPrivateKey)?;
+    keypair_from_seed(&seed)
+}
+
+fn find_pending(wallet: &MultisigWallet, tx_id: &str) -> Result<Transaction> {
+    wallet
+        .pending_transactions()
+        .into_iter()
+        .find(|tx| tx.id == tx_id)
+        .cloned()
+        .ok_or_else(|| MultisigError::TransactionNotFound {
+            tx_id: tx_id.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_args(args: &[&str]) -> Result<String> {
+        let mut out = Vec::new();
+        run(Cli::parse_from(args), &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn keygen() -> (String, String) {
+        let output = run_args(&["multisig-rs", "keygen"]).unwrap();
+        let field = |name: &str| {
+            output
+                .lines()
+                .find_map(|line| line.strip_prefix(name))
+                .unwrap()
+                .to_string()
+        };
+        (field("secret: "), field("public: "))
+    }
+
+    #[test]
+    fn test_cli_workflow() {
+        let path =
+            std::env::temp_dir().join(format!("multisig-cli-{}.json", rand::random::<u64>()));
+        let wallet = path.to_str().unwrap();
+        let (sk1, pk1) = keygen();
+        let (sk2, pk2) = keygen();
+        let keys = format!("{},{}", pk1, pk2);
+
+        run_args(&[
+            "multisig-rs",
+            "--wallet",
+            wallet,
+            "create-wallet",
+            "--threshold",
+            "2",
+            "--keys",
+            &keys,
+        ])
+        .unwrap();
+        assert!(run_args(&[
+            "multisig-rs",
+            "--wallet",
+            wallet,
+            "create-wallet",
+            "--threshold",
+            "1",
+            "--keys",
+            &pk1
+        ])
+        .is_err());
+
+        let tx_id = run_args(&[
+            "multisig-rs",
+            "--wallet",
+            wallet,
+            "propose",
+            "--amount",
+            "500",
+            "--recipient",
+            "alice",
+        ])
+        .unwrap()
+        .trim()
+        .to_string();
+
+        run_args(&[
+            "multisig-rs",
+            "--wallet",
+            wallet,
+            "sign",
+            "--tx-id",
+            &tx_id,
+            "--key",
+            &sk1,
+        ])
+        .unwrap();
+        assert!(matches!(
+            run_args(&[
+                "multisig-rs",
+                "--wallet",
+                wallet,
+                "execute",
+                "--tx-id",
+                &tx_id
+            ]),
+            Err(MultisigError::InsufficientSignatures { .. })
+        ));
+        run_args(&[
+            "multisig-rs",
+            "--wallet",
+            wallet,
+            "sign",
+            "--tx-id",
+            &tx_id,
+            "--key",
+            &sk2,
+        ])
+        .unwrap();
+
+        let status = run_args(&[
+            "multisig-rs",
+            "--wallet",
+            wallet,
+            "status",
+            "--tx-id",
+            &tx_id,
+        ])
+        .unwrap();
+        assert!(status.contains("\"is_ready\": true"));
+
+        let executed = run_args(&[
+            "multisig-rs",
+            "--wallet",
+            wallet,
+            "execute",
+            "--tx-id",
+            &tx_id,
+        ])
+        .unwrap();
+        assert!(executed.starts_with(&format!("executed {}", tx_id)));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use crate::transaction::current_timestamp;
+
+/// Source of the current time for wallet checks
+pub trait Clock: Send + Sync {
+    /// Current Unix time in seconds
+    fn now_unix(&self) -> u64;
+}
+
+/// The system clock, used by default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_unix(&self) -> u64 {
+        current_timestamp()
+    }
+}
+
+/// A manually controlled clock for deterministic tests
+///
+/// Clones share the same time, so a test can keep one and move the time of
+/// a wallet it handed another to.
+#[derive(Debug, Clone, Default)]
+pub struct FixedClock(Arc<AtomicU64>);
+
+impl FixedClock {
+    /// Create a clock stopped at `now`
+    pub fn new(now: u64) -> Self {
+        FixedClock(Arc::new(AtomicU64::new(now)))
+    }
+
+    /// Move the clock to `now`
+    pub fn set(&self, now: u64) {
+        self.0.store(now, Ordering::SeqCst);
+    }
+
+    /// Move the clock forward by `seconds`
+    pub fn advance(&self, seconds: u64) {
+        self.0.fetch_add(seconds, Ordering::SeqCst);
+    }
+}
+
+impl Clock for FixedClock {
+    fn now_unix(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_clock_is_shared() {
+        let clock = FixedClock::new(1_000);
+        let handle = clock.clone();
+        handle.advance(60);
+        assert_eq!(clock.now_unix(), 1_060);
+        handle.set(5);
+        assert_eq!(clock.now_unix(), 5);
+        assert!(SystemClock.now_unix() > 1_600_000_000);
+    }
+}
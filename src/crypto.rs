@@ -1,43 +1,272 @@
-use secp256k1::{Secp256k1, SecretKey, PublicKey, Message, ecdsa::Signature};
+use std::sync::{atomic, OnceLock};
+use secp256k1::{All, Keypair, Secp256k1, SecretKey, PublicKey, Message, Verification, XOnlyPublicKey, ecdsa::Signature, schnorr};
+use secp256k1::ecdsa::RecoverableSignature;
 use rand::rngs::OsRng;
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
+use sha3::Keccak256;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 use crate::error::{MultisigError, Result};
 
-/// Generate a new keypair for signing
+/// Signature algorithm a wallet expects its signers to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SignatureScheme {
+    #[default]
+    Ecdsa,
+    /// BIP-340 Schnorr over the signer's x-only public key
+    Schnorr,
+}
+
+/// Digest applied to a message before it is signed or verified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    /// SHA-256 applied twice, as Bitcoin does
+    DoubleSha256,
+    /// Original Keccak-256 as used by Ethereum, not NIST SHA3-256
+    Keccak256,
+}
+
+/// How a payload is framed before hashing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MessageMode {
+    /// The payload is hashed as is
+    #[default]
+    Standard,
+    /// EIP-191 `personal_sign` framing, always hashed with Keccak-256
+    Eip191,
+}
+
+/// Serialized form of a secp256k1 public key
+///
+/// Wallets store and compare keys in compressed form; uncompressed keys
+/// from external systems are accepted and normalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PubkeyEncoding {
+    /// 33 bytes: parity prefix and x coordinate
+    #[default]
+    Compressed,
+    /// 65 bytes: `0x04` prefix and both coordinates
+    Uncompressed,
+}
+
+impl PubkeyEncoding {
+    /// Identify the encoding of serialized key bytes from their length
+    pub fn detect(bytes: &[u8]) -> Result<Self> {
+        match bytes.len() {
+            33 => Ok(PubkeyEncoding::Compressed),
+            65 => Ok(PubkeyEncoding::Uncompressed),
+            _ => Err(MultisigError::InvalidPublicKey { index: None }),
+        }
+    }
+
+    /// Serialize a public key in this encoding
+    pub fn encode(self, public_key: &PublicKey) -> Vec<u8> {
+        match self {
+            PubkeyEncoding::Compressed => public_key.serialize().to_vec(),
+            PubkeyEncoding::Uncompressed => public_key.serialize_uncompressed().to_vec(),
+        }
+    }
+}
+
+/// Shared secp256k1 context
+///
+/// Creating a context allocates and randomizes its tables, so it is built
+/// once on first use and reused by every crypto function.
+pub(crate) fn context() -> &'static Secp256k1<All> {
+    static CONTEXT: OnceLock<Secp256k1<All>> = OnceLock::new();
+    CONTEXT.get_or_init(Secp256k1::new)
+}
 
+/// Generate a new keypair for signing
 pub fn generate_keypair() -> Result<(SecretKey, PublicKey)> {
-    let secp = Secp256k1::new();
-    let (secret_key, public_key) = secp.generate_keypair(&mut OsRng);
+    generate_keypair_from_rng(&mut OsRng)
+}
+
+/// Generate a keypair from a caller-supplied entropy source
+///
+/// A failing RNG is reported as `CryptoError` instead of panicking.
+pub fn generate_keypair_from_rng<R: Rng + CryptoRng + ?Sized>(
+    rng: &mut R,
+) -> Result<(SecretKey, PublicKey)> {
+    let mut bytes = [0u8; 32];
+    loop {
+        rng.try_fill_bytes(&mut bytes)
+            .map_err(|e| MultisigError::CryptoError(format!("random number generator failed: {}", e)))?;
+
+        // Out-of-range scalars are astronomically unlikely; draw again
+        if let Ok(secret_key) = SecretKey::from_slice(&bytes) {
+            let public_key = PublicKey::from_secret_key(context(), &secret_key);
+            return Ok((secret_key, public_key));
+        }
+    }
+}
+
+/// A `SecretKey` that is wiped from memory when dropped
+///
+/// Derefs to the inner key, so it can be passed anywhere a `&SecretKey` is
+/// expected. `SecretKey` cannot hold an all-zero scalar, so wiping
+/// overwrites it with a fixed dummy key using volatile writes.
+#[derive(Debug)]
+pub struct ManagedSecretKey(SecretKey);
+
+impl ManagedSecretKey {
+    /// Take ownership of a secret key so it is wiped on drop
+    pub fn new(secret_key: SecretKey) -> Self {
+        ManagedSecretKey(secret_key)
+    }
+}
+
+impl std::ops::Deref for ManagedSecretKey {
+    type Target = SecretKey;
+
+    fn deref(&self) -> &SecretKey {
+        &self.0
+    }
+}
+
+impl Zeroize for ManagedSecretKey {
+    fn zeroize(&mut self) {
+        self.0.non_secure_erase();
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl Drop for ManagedSecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for ManagedSecretKey {}
+
+/// Generate a keypair whose secret key is wiped when dropped
+pub fn generate_managed_keypair() -> Result<(ManagedSecretKey, PublicKey)> {
+    let (secret_key, public_key) = generate_keypair()?;
+    Ok((ManagedSecretKey::new(secret_key), public_key))
+}
+
+/// Derive a keypair deterministically from a 32-byte seed
+///
+/// The seed is used directly as the secret scalar, so this is meant for
+/// tests and fixtures rather than production key generation.
+pub fn keypair_from_seed(seed: &[u8; 32]) -> Result<(SecretKey, PublicKey)> {
+    let secret_key = SecretKey::from_slice(seed)?;
+    let public_key = PublicKey::from_secret_key(context(), &secret_key);
     Ok((secret_key, public_key))
 }
 
-/// Sign a message with a private key
+/// Check in constant time whether a secret key belongs to a public key
+///
+/// The derived public key is compared byte-for-byte with `subtle`, so the
+/// running time does not reveal how many leading bytes matched.
+pub fn secret_key_matches_pubkey(secret_key: &SecretKey, public_key: &PublicKey) -> bool {
+    let derived = PublicKey::from_secret_key(context(), secret_key);
+    derived.serialize().ct_eq(&public_key.serialize()).into()
+}
 
+/// Sign a message with a private key
 pub fn sign_message(message: &[u8], secret_key: &SecretKey) -> Result<Signature> {
-    let secp = Secp256k1::new();
+    sign_message_with(HashAlgorithm::Sha256, message, secret_key)
+}
+
+/// Sign a message hashed with the given algorithm
+pub fn sign_message_with(
+    algorithm: HashAlgorithm,
+    message: &[u8],
+    secret_key: &SecretKey,
+) -> Result<Signature> {
+    let secp = context();
     
     // Hash the message
-    let hash = hash_message(message);
-    let message = Message::from_digest_slice(&hash)
-        .map_err(|e| MultisigError::CryptoError(e.to_string()))?;
+    let message = digest_message(algorithm, message);
     
-    Ok(secp.sign_ecdsa(&message, secret_key))
+    let mut signature = secp.sign_ecdsa(&message, secret_key);
+    normalize_signature(&mut signature);
+    Ok(signature)
 }
 
-/// Verify a signature against a public key
+/// Convert a signature to its low-S form in place
+///
+/// Bitcoin consensus and many verifiers only accept low-S signatures.
+pub fn normalize_signature(sig: &mut Signature) {
+    sig.normalize_s();
+}
 
+/// Check whether a signature is already in low-S form
+pub fn is_low_s(sig: &Signature) -> bool {
+    let mut normalized = *sig;
+    normalized.normalize_s();
+    normalized == *sig
+}
+
+/// Sign a message with a private key, producing a reproducible signature
+///
+/// The nonce is derived from the key and message hash per RFC 6979, so the
+/// same inputs always yield byte-identical signatures. This is what
+/// `sign_message` does as well; the separate name documents the guarantee
+/// for callers that depend on it.
+pub fn sign_message_deterministic(message: &[u8], secret_key: &SecretKey) -> Result<Signature> {
+    sign_message(message, secret_key)
+}
+
+/// Verify a signature against a public key
 pub fn verify_signature(
     message: &[u8],
     signature: &Signature,
     public_key: &PublicKey,
 ) -> Result<bool> {
-    let secp = Secp256k1::new();
-    
-    // Hash the message
+    verify_signature_with(HashAlgorithm::Sha256, message, signature, public_key)
+}
+
+/// Verify a signature over a message hashed with the given algorithm
+pub fn verify_signature_with(
+    algorithm: HashAlgorithm,
+    message: &[u8],
+    signature: &Signature,
+    public_key: &PublicKey,
+) -> Result<bool> {
+    let message = digest_message(algorithm, message);
+
+    Ok(context().verify_ecdsa(&message, signature, public_key).is_ok())
+}
+
+/// Frame a message as EIP-191 version `0x45` (`personal_sign`) does
+pub fn eip191_message(message: &[u8]) -> Vec<u8> {
+    let mut framed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    framed.extend_from_slice(message);
+    framed
+}
+
+/// Sign a message the way ethers and web3 `personal_sign` do
+pub fn sign_message_eip191(message: &[u8], secret_key: &SecretKey) -> Result<Signature> {
+    sign_message_with(HashAlgorithm::Keccak256, &eip191_message(message), secret_key)
+}
 
-    let hash = hash_message(message);
-    let message = Message::from_digest_slice(&hash)
-        .map_err(|e| MultisigError::CryptoError(e.to_string()))?;
+/// Verify a signature produced by `sign_message_eip191` or `personal_sign`
+pub fn verify_signature_eip191(
+    message: &[u8],
+    signature: &Signature,
+    public_key: &PublicKey,
+) -> Result<bool> {
+    let framed = eip191_message(message);
+    verify_signature_with(HashAlgorithm::Keccak256, &framed, signature, public_key)
+}
+
+/// Verify a signature using a caller-provided context
+///
+/// Building a context is expensive, so callers verifying many signatures
+/// should create one and reuse it.
+pub fn verify_signature_with_ctx<C: Verification>(
+    secp: &Secp256k1<C>,
+    message: &[u8],
+    signature: &Signature,
+    public_key: &PublicKey,
+) -> Result<bool> {
+    let message = digest_message(HashAlgorithm::Sha256, message);
     
     match secp.verify_ecdsa(&message, signature, public_key) {
         Ok(_) => Ok(true),
@@ -45,18 +274,137 @@ pub fn verify_signature(
     }
 }
 
-/// Hash a message using SHA-256
+/// Sign a message with a recoverable ECDSA signature
+///
+/// The signer's public key can be recovered from the signature and message
+/// with `recover_public_key`.
+pub fn sign_message_recoverable(
+    message: &[u8],
+    secret_key: &SecretKey,
+) -> Result<RecoverableSignature> {
+    sign_message_recoverable_with(HashAlgorithm::Sha256, message, secret_key)
+}
+
+/// Sign a message hashed with the given algorithm, recoverably
+pub fn sign_message_recoverable_with(
+    algorithm: HashAlgorithm,
+    message: &[u8],
+    secret_key: &SecretKey,
+) -> Result<RecoverableSignature> {
+    let message = digest_message(algorithm, message);
+
+    Ok(context().sign_ecdsa_recoverable(&message, secret_key))
+}
 
-pub fn hash_message(message: &[u8]) -> [u8; 32] {
+/// Recover the public key that produced a recoverable signature
+pub fn recover_public_key(message: &[u8], signature: &RecoverableSignature) -> Result<PublicKey> {
+    recover_public_key_with(HashAlgorithm::Sha256, message, signature)
+}
+
+/// Recover the signer of a message hashed with the given algorithm
+pub fn recover_public_key_with(
+    algorithm: HashAlgorithm,
+    message: &[u8],
+    signature: &RecoverableSignature,
+) -> Result<PublicKey> {
+    let message = digest_message(algorithm, message);
+
+    Ok(context().recover_ecdsa(&message, signature)?)
+}
+
+/// Sign a message with a BIP-340 Schnorr signature
+pub fn sign_message_schnorr(message: &[u8], keypair: &Keypair) -> Result<schnorr::Signature> {
+    sign_message_schnorr_with(HashAlgorithm::Sha256, message, keypair)
+}
+
+/// Sign a message hashed with the given algorithm using BIP-340 Schnorr
+pub fn sign_message_schnorr_with(
+    algorithm: HashAlgorithm,
+    message: &[u8],
+    keypair: &Keypair,
+) -> Result<schnorr::Signature> {
+    let message = digest_message(algorithm, message);
+
+    Ok(context().sign_schnorr_with_rng(&message, keypair, &mut OsRng))
+}
+
+/// Verify a BIP-340 Schnorr signature against an x-only public key
+pub fn verify_signature_schnorr(
+    message: &[u8],
+    signature: &schnorr::Signature,
+    public_key: &XOnlyPublicKey,
+) -> Result<bool> {
+    verify_signature_schnorr_with(HashAlgorithm::Sha256, message, signature, public_key)
+}
+
+/// Verify a Schnorr signature over a message hashed with the given algorithm
+pub fn verify_signature_schnorr_with(
+    algorithm: HashAlgorithm,
+    message: &[u8],
+    signature: &schnorr::Signature,
+    public_key: &XOnlyPublicKey,
+) -> Result<bool> {
+    let message = digest_message(algorithm, message);
+
+    Ok(context().verify_schnorr(signature, &message, public_key).is_ok())
+}
+
+// Every supported hasher must produce exactly one secp256k1 message digest
+const _: () = assert!(DIGEST_LEN == secp256k1::constants::MESSAGE_SIZE);
+
+/// Length of the digests produced by `hash_message_with`
+pub const DIGEST_LEN: usize = 32;
+
+/// Hash a message and wrap the digest for signing or verification
+///
+/// Going through a fixed-size array means a hasher returning the wrong
+/// length fails to compile instead of failing at runtime.
+fn digest_message(algorithm: HashAlgorithm, message: &[u8]) -> Message {
+    let digest: [u8; DIGEST_LEN] = hash_message_with(algorithm, message);
+    debug_assert_eq!(digest.len(), secp256k1::constants::MESSAGE_SIZE);
+    Message::from_digest(digest)
+}
+
+/// Hash a message using SHA-256
+pub fn hash_message(message: &[u8]) -> [u8; DIGEST_LEN] {
     let mut hasher = Sha256::new();
     hasher.update(message);
     hasher.finalize().into()
 }
 
+/// Hash a message using the given algorithm
+pub fn hash_message_with(algorithm: HashAlgorithm, message: &[u8]) -> [u8; DIGEST_LEN] {
+    match algorithm {
+        HashAlgorithm::Sha256 => hash_message(message),
+        HashAlgorithm::DoubleSha256 => hash_message(&hash_message(message)),
+        HashAlgorithm::Keccak256 => Keccak256::digest(message).into(),
+    }
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    /// Re-encode a signature with `s` replaced by `n - s`, the high-S twin
+    /// that verifies the same message
+    pub(crate) fn high_s_form(sig: &Signature) -> Signature {
+        const ORDER: [u8; 32] = [
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+            0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b,
+            0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+        ];
+
+        let mut compact = sig.serialize_compact();
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let diff = ORDER[i] as i16 - compact[32 + i] as i16 - borrow;
+            borrow = (diff < 0) as i16;
+            compact[32 + i] = diff.rem_euclid(256) as u8;
+        }
+        Signature::from_compact(&compact).unwrap()
+    }
+
     #[test]
     fn test_keypair_generation() {
         let result = generate_keypair();
@@ -85,4 +433,227 @@ mod tests {
         
         assert!(!is_valid);
     }
+
+    #[test]
+    fn test_shared_context_is_reused() {
+        assert!(std::ptr::eq(context(), context()));
+    }
+
+    #[test]
+    fn test_deterministic_signing() {
+        let (secret_key, public_key) = generate_keypair().unwrap();
+        let tx = crate::transaction::Transaction::new("recipient".to_string(), 1000, None);
+        let message = tx.to_bytes();
+
+        let first = sign_message_deterministic(&message, &secret_key).unwrap();
+        let second = sign_message_deterministic(&message, &secret_key).unwrap();
+
+        assert_eq!(first.serialize_compact(), second.serialize_compact());
+        assert_eq!(first, sign_message(&message, &secret_key).unwrap());
+        assert!(verify_signature(&message, &first, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_schnorr_sign_and_verify() {
+        let (secret_key, _) = generate_keypair().unwrap();
+        let keypair = Keypair::from_secret_key(context(), &secret_key);
+        let (x_only, _) = keypair.x_only_public_key();
+        let message = b"Hello, schnorr world!";
+
+        let signature = sign_message_schnorr(message, &keypair).unwrap();
+        assert!(verify_signature_schnorr(message, &signature, &x_only).unwrap());
+        assert!(!verify_signature_schnorr(b"Tampered", &signature, &x_only).unwrap());
+    }
+
+    #[test]
+    fn test_recoverable_signature() {
+        let (secret_key, public_key) = generate_keypair().unwrap();
+        let message = b"Recover me";
+
+        let signature = sign_message_recoverable(message, &secret_key).unwrap();
+        assert_eq!(recover_public_key(message, &signature).unwrap(), public_key);
+        assert_ne!(recover_public_key(b"Other message", &signature).unwrap(), public_key);
+    }
+
+    #[test]
+    fn test_low_s_normalization() {
+        let (secret_key, _) = generate_keypair().unwrap();
+        let signature = sign_message(b"Low S please", &secret_key).unwrap();
+        assert!(is_low_s(&signature));
+
+        let mut high = high_s_form(&signature);
+        assert!(!is_low_s(&high));
+
+        normalize_signature(&mut high);
+        assert_eq!(high, signature);
+    }
+
+    #[test]
+    fn test_keypair_from_seeded_rng() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let first = generate_keypair_from_rng(&mut StdRng::seed_from_u64(7)).unwrap();
+        let second = generate_keypair_from_rng(&mut StdRng::seed_from_u64(7)).unwrap();
+        assert_eq!(first, second);
+        assert!(secret_key_matches_pubkey(&first.0, &first.1));
+    }
+
+    #[test]
+    fn test_failing_rng_is_an_error() {
+        struct BrokenRng;
+
+        impl rand::RngCore for BrokenRng {
+            fn next_u32(&mut self) -> u32 {
+                let mut bytes = [0u8; 4];
+                self.fill_bytes(&mut bytes);
+                u32::from_le_bytes(bytes)
+            }
+            fn next_u64(&mut self) -> u64 {
+                let mut bytes = [0u8; 8];
+                self.fill_bytes(&mut bytes);
+                u64::from_le_bytes(bytes)
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.try_fill_bytes(dest).expect("BrokenRng never yields bytes")
+            }
+            fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> std::result::Result<(), rand::Error> {
+                Err(rand::Error::new("entropy unavailable"))
+            }
+        }
+        impl CryptoRng for BrokenRng {}
+
+        assert!(matches!(
+            generate_keypair_from_rng(&mut BrokenRng),
+            Err(MultisigError::CryptoError(_))
+        ));
+    }
+
+    #[test]
+    fn test_keypair_from_seed() {
+        let mut seed = [0u8; 32];
+        assert!(matches!(keypair_from_seed(&seed), Err(MultisigError::InvalidPrivateKey)));
+
+        // A secret scalar of one yields the curve generator
+        seed[31] = 1;
+        let (_, public_key) = keypair_from_seed(&seed).unwrap();
+        assert_eq!(
+            hex::encode(public_key.serialize()),
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+        assert_eq!(keypair_from_seed(&seed).unwrap(), keypair_from_seed(&seed).unwrap());
+    }
+
+    #[test]
+    fn test_hash_algorithm_vectors() {
+        assert_eq!(
+            hex::encode(hash_message_with(HashAlgorithm::Sha256, b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex::encode(hash_message_with(HashAlgorithm::DoubleSha256, b"abc")),
+            "4f8b42c22dd3729b519ba6f68d2da7cc5b2d606d05daed5ad5128cc03e6c6358"
+        );
+        assert_eq!(
+            hex::encode(hash_message_with(HashAlgorithm::Keccak256, b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[test]
+    fn test_sign_and_verify_with_algorithm() {
+        let (secret_key, public_key) = generate_keypair().unwrap();
+        let message = b"Hashed differently";
+
+        let signature = sign_message_with(HashAlgorithm::Keccak256, message, &secret_key).unwrap();
+        assert!(verify_signature_with(HashAlgorithm::Keccak256, message, &signature, &public_key).unwrap());
+        assert!(!verify_signature(message, &signature, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_pubkey_encoding() {
+        let (_, public_key) = generate_keypair().unwrap();
+
+        let compressed = PubkeyEncoding::Compressed.encode(&public_key);
+        let uncompressed = PubkeyEncoding::Uncompressed.encode(&public_key);
+        assert_eq!(PubkeyEncoding::detect(&compressed).unwrap(), PubkeyEncoding::Compressed);
+        assert_eq!(PubkeyEncoding::detect(&uncompressed).unwrap(), PubkeyEncoding::Uncompressed);
+        assert!(matches!(
+            PubkeyEncoding::detect(&uncompressed[..64]),
+            Err(MultisigError::InvalidPublicKey { index: None })
+        ));
+    }
+
+    #[test]
+    fn test_secret_key_matches_pubkey() {
+        let (secret_key, public_key) = generate_keypair().unwrap();
+        let (_, other_public_key) = generate_keypair().unwrap();
+
+        assert!(secret_key_matches_pubkey(&secret_key, &public_key));
+        assert!(!secret_key_matches_pubkey(&secret_key, &other_public_key));
+    }
+
+    #[test]
+    fn test_managed_secret_key() {
+        let (mut secret_key, public_key) = generate_managed_keypair().unwrap();
+        let message = b"Managed key";
+
+        let signature = sign_message(message, &secret_key).unwrap();
+        assert!(verify_signature(message, &signature, &public_key).unwrap());
+
+        let original = secret_key.secret_bytes();
+        secret_key.zeroize();
+        assert_ne!(secret_key.secret_bytes(), original);
+        assert!(!secret_key_matches_pubkey(&secret_key, &public_key));
+    }
+
+    #[test]
+    fn test_digest_message_for_every_hasher() {
+        let algorithms = [
+            HashAlgorithm::Sha256,
+            HashAlgorithm::DoubleSha256,
+            HashAlgorithm::Keccak256,
+        ];
+        for algorithm in algorithms {
+            let digest = hash_message_with(algorithm, b"digest");
+            assert_eq!(digest_message(algorithm, b"digest"), Message::from_digest(digest));
+        }
+    }
+
+    #[test]
+    fn test_eip191_vectors() {
+        // ethers `hashMessage("hello world")`
+        let framed = eip191_message(b"hello world");
+        assert_eq!(
+            hex::encode(hash_message_with(HashAlgorithm::Keccak256, &framed)),
+            "d9eba16ed0ecae432b71fe008c98cc872bb4cc214d3220a36f365326cf807d68"
+        );
+
+        let key_hex = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+        let secret_key = SecretKey::from_slice(&hex::decode(key_hex).unwrap()).unwrap();
+        let public_key = PublicKey::from_secret_key(context(), &secret_key);
+        let message = "I♥SF".as_bytes();
+
+        let signature = sign_message_eip191(message, &secret_key).unwrap();
+        assert_eq!(
+            hex::encode(signature.serialize_compact()),
+            "87f6856afaa7684bc6ecccef96246c155da984e7889dbdda1ae59940bdab8e67\
+             6cd3055bd38ac073cd5710e3040f1ed08073ca026266caeb097213d52665c4c0"
+        );
+        assert!(verify_signature_eip191(message, &signature, &public_key).unwrap());
+        assert!(!verify_signature(message, &signature, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_secp256k1_errors_keep_their_kind() {
+        let bad_key = PublicKey::from_slice(&[0x05; 33]).unwrap_err();
+        assert!(matches!(
+            MultisigError::from(bad_key),
+            MultisigError::InvalidPublicKey { index: None }
+        ));
+
+        assert!(matches!(keypair_from_seed(&[0u8; 32]), Err(MultisigError::InvalidPrivateKey)));
+
+        let bad_signature = Signature::from_compact(&[0u8; 10]).unwrap_err();
+        assert!(matches!(MultisigError::from(bad_signature), MultisigError::MalformedSignature));
+    }
 }
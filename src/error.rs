@@ -3,38 +3,146 @@ use thiserror::Error;
 /// Custom error types for the multisig wallet
 #[derive(Error, Debug)]
 pub enum MultisigError {
-    #[error("Invalid signature")]
-    InvalidSignature,
+    #[error("Invalid signature from {pubkey_hex} on transaction {tx_id}")]
+    InvalidSignature { tx_id: String, pubkey_hex: String },
 
     #[error("Insufficient signatures: required {required}, got {actual}")]
     InsufficientSignatures { required: usize, actual: usize },
 
-    #[error("Signer not authorized")]
-    UnauthorizedSigner,
+    #[error("Required signers have not signed: {}", .missing.join(", "))]
+    MissingRequiredSigners { missing: Vec<String> },
+
+    #[error("Signer {pubkey_hex} not authorized")]
+    UnauthorizedSigner { pubkey_hex: String },
 
     #[error("Transaction already executed")]
     TransactionAlreadyExecuted,
 
+    #[error("Transaction id does not match its contents")]
+    TransactionIdMismatch,
+
+    #[error("Transaction already proposed")]
+    DuplicateTransaction,
+
+    #[error("Nonce {nonce} was already used for recipient {recipient}")]
+    DuplicateNonce { recipient: String, nonce: u64 },
+
+    #[error("Amount {amount} exceeds the limit of {limit}")]
+    AmountExceedsLimit { amount: u128, limit: u128 },
+
+    #[error("Amount total overflowed")]
+    AmountOverflow,
+
+    #[error("Zero-amount transactions are not allowed")]
+    ZeroAmount,
+
+    #[error("Wallet already holds the maximum of {limit} transactions")]
+    PendingLimitReached { limit: usize },
+
+    #[error("Invalid recipient")]
+    InvalidRecipient,
+
     #[error("Invalid threshold: M={m} must be <= N={n}")]
     InvalidThreshold { m: usize, n: usize },
 
+    #[error("Signer weight must be greater than zero")]
+    InvalidWeight,
+
+    #[error("Malformed signature")]
+    MalformedSignature,
+
+    #[error("Signature is not in canonical low-S form")]
+    NonCanonicalSignature,
+
+    #[error("Signature scheme does not match the wallet")]
+    SignatureSchemeMismatch,
+
     #[error("Duplicate signature detected")]
     DuplicateSignature,
 
+    #[error("Signer already authorized")]
+    DuplicateSigner,
+
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 
+    #[error("Binary encoding error: {0}")]
+    BincodeError(String),
+
+    #[error("Invalid base64: {0}")]
+    Base64Error(#[from] base64::DecodeError),
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
     #[error("Cryptographic error: {0}")]
     CryptoError(String),
 
-    #[error("Invalid public key")]
-    InvalidPublicKey,
+    #[error("Invalid public key{}", .index.map(|i| format!(" at index {i}")).unwrap_or_default())]
+    InvalidPublicKey { index: Option<usize> },
+
+    #[error("Signer keys could not be aggregated")]
+    KeyAggregationFailed,
 
     #[error("Invalid private key")]
     InvalidPrivateKey,
 
-    #[error("Transaction not found")]
-    TransactionNotFound,
+    #[error("Invalid partial transaction: {0}")]
+    InvalidPartialTransaction(String),
+
+    #[error("Encrypted metadata could not be decrypted with this key")]
+    DecryptionFailed,
+
+    #[error("Invalid mnemonic phrase")]
+    InvalidMnemonic,
+
+    #[error("Account {name} already exists")]
+    AccountExists { name: String },
+
+    #[error("Account {name} not found")]
+    AccountNotFound { name: String },
+
+    #[error("Transaction {tx_id} not found")]
+    TransactionNotFound { tx_id: String },
+
+    #[error("Transaction timestamp {timestamp} is outside the accepted range")]
+    TimestampOutOfRange { timestamp: u64 },
+
+    #[error("Transaction is time-locked until {valid_at}")]
+    TransactionNotYetValid { valid_at: u64 },
+
+    #[error("Transaction expired")]
+    TransactionExpired,
+
+    #[error("Transaction rejected")]
+    TransactionRejected,
+
+    #[error("Signer already rejected this transaction")]
+    DuplicateRejection,
+
+    #[error("{} signatures in the batch failed, {added} added", .failures.len())]
+    BatchFailed {
+        added: usize,
+        failures: Vec<(usize, MultisigError)>,
+    },
+}
+
+/// Keep the meaning of secp256k1 failures that callers can act on
+///
+/// Signature parse failures become `MalformedSignature`, since
+/// `InvalidSignature` names the transaction and signer that failed to verify.
+impl From<secp256k1::Error> for MultisigError {
+    fn from(error: secp256k1::Error) -> Self {
+        match error {
+            secp256k1::Error::InvalidPublicKey => MultisigError::InvalidPublicKey { index: None },
+            secp256k1::Error::InvalidSecretKey => MultisigError::InvalidPrivateKey,
+            secp256k1::Error::InvalidSignature | secp256k1::Error::IncorrectSignature => {
+                MultisigError::MalformedSignature
+            }
+            secp256k1::Error::InvalidPublicKeySum => MultisigError::KeyAggregationFailed,
+            other => MultisigError::CryptoError(other.to_string()),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, MultisigError>;
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+/// An entry in a wallet's append-only audit log
+///
+/// Timestamps are Unix seconds; signers are identified by pubkey hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WalletEvent {
+    TransactionProposed {
+        tx_id: String,
+        timestamp: u64,
+    },
+    SignatureAdded {
+        tx_id: String,
+        signer: String,
+        timestamp: u64,
+    },
+    /// A signer's earlier signature was overwritten by a new one
+    SignatureReplaced {
+        tx_id: String,
+        signer: String,
+        timestamp: u64,
+    },
+    TransactionExecuted {
+        tx_id: String,
+        timestamp: u64,
+    },
+    TransactionCancelled {
+        tx_id: String,
+        timestamp: u64,
+    },
+}
+
+impl WalletEvent {
+    /// Id of the transaction the event refers to
+    pub fn tx_id(&self) -> &str {
+        match self {
+            WalletEvent::TransactionProposed { tx_id, .. }
+            | WalletEvent::SignatureAdded { tx_id, .. }
+            | WalletEvent::SignatureReplaced { tx_id, .. }
+            | WalletEvent::TransactionExecuted { tx_id, .. }
+            | WalletEvent::TransactionCancelled { tx_id, .. } => tx_id,
+        }
+    }
+
+    /// Unix time at which the event was recorded
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            WalletEvent::TransactionProposed { timestamp, .. }
+            | WalletEvent::SignatureAdded { timestamp, .. }
+            | WalletEvent::SignatureReplaced { timestamp, .. }
+            | WalletEvent::TransactionExecuted { timestamp, .. }
+            | WalletEvent::TransactionCancelled { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_serialization() {
+        let event = WalletEvent::SignatureAdded {
+            tx_id: "abc".to_string(),
+            signer: "02ff".to_string(),
+            timestamp: 42,
+        };
+
+        let json = serde_json::to_string(&event).unwrap();
+        let restored: WalletEvent = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, event);
+        assert_eq!(restored.tx_id(), "abc");
+        assert_eq!(restored.timestamp(), 42);
+    }
+}
@@ -0,0 +1,78 @@
+use std::str::FromStr;
+use bitcoin::bip32::{DerivationPath, Xpriv};
+use bitcoin::NetworkKind;
+use secp256k1::{PublicKey, SecretKey};
+use crate::crypto::context;
+use crate::error::{MultisigError, Result};
+
+/// Create a base58 master extended private key from a BIP32 seed
+pub fn master_from_seed(seed: &[u8]) -> Result<String> {
+    let master = Xpriv::new_master(NetworkKind::Main, seed)
+        .map_err(|e| MultisigError::CryptoError(e.to_string()))?;
+    Ok(master.to_string())
+}
+
+/// Derive a signer keypair from an extended private key
+///
+/// `xprv` is a base58 extended private key and `path` a BIP32 derivation
+/// path such as `m/48'/0'/0'/2'`.
+pub fn derive_child(xprv: &str, path: &str) -> Result<(SecretKey, PublicKey)> {
+    let xprv = Xpriv::from_str(xprv).map_err(|_| MultisigError::InvalidPrivateKey)?;
+    let path = DerivationPath::from_str(path)
+        .map_err(|e| MultisigError::CryptoError(e.to_string()))?;
+
+    let secp = context();
+    let child = xprv
+        .derive_priv(secp, &path)
+        .map_err(|e| MultisigError::CryptoError(e.to_string()))?;
+
+    let secret_key = child.private_key;
+    Ok((secret_key, PublicKey::from_secret_key(secp, &secret_key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{sign_message, verify_signature};
+    use crate::wallet::MultisigWallet;
+
+    // BIP32 test vector 1
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+    const MASTER: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+    #[test]
+    fn test_bip32_test_vector() {
+        let master = master_from_seed(&hex::decode(SEED).unwrap()).unwrap();
+        assert_eq!(master, MASTER);
+
+        let (secret_key, public_key) = derive_child(MASTER, "m/0'").unwrap();
+        assert_eq!(
+            hex::encode(secret_key.secret_bytes()),
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea"
+        );
+        assert_eq!(
+            hex::encode(public_key.serialize()),
+            "035a784662a4a20a65bf6aab9ae98a6c068a81c52e4b032c0fb5400c706cfccc56"
+        );
+    }
+
+    #[test]
+    fn test_derived_keys_sign_for_wallet() {
+        let (sk1, pk1) = derive_child(MASTER, "m/48'/0'/0'/2'").unwrap();
+        let (_, pk2) = derive_child(MASTER, "m/48'/0'/1'/2'").unwrap();
+        assert_eq!(
+            hex::encode(pk1.serialize()),
+            "02f8e344630de7c05a003f854b5ce132beb6497d4e7093355033c000257ef1e074"
+        );
+
+        let signature = sign_message(b"Derived", &sk1).unwrap();
+        assert!(verify_signature(b"Derived", &signature, &pk1).unwrap());
+        assert!(MultisigWallet::new(2, vec![pk1, pk2]).is_ok());
+
+        assert!(derive_child(MASTER, "not/a/path").is_err());
+        assert!(matches!(
+            derive_child("xprv-garbage", "m/0"),
+            Err(MultisigError::InvalidPrivateKey)
+        ));
+    }
+}
@@ -1,9 +1,40 @@
 pub mod wallet;
+pub mod builder;
 pub mod transaction;
 pub mod crypto;
+pub mod clock;
 pub mod error;
+pub mod events;
+pub mod policy;
+pub mod registry;
+pub mod verify;
+pub mod shared;
+pub mod warnings;
+#[cfg(feature = "hd")]
+pub mod hd;
+#[cfg(feature = "backup")]
+pub mod backup;
+#[cfg(feature = "psbt")]
+pub mod psbt;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "memo")]
+pub mod memo;
 
-pub use wallet::MultisigWallet;
-pub use transaction::Transaction;
-pub use crypto::{generate_keypair, sign_message, verify_signature};
+pub use wallet::{DuplicatePolicy, MultisigWallet, StoredSignature};
+pub use builder::MultisigWalletBuilder;
+pub use transaction::{HashId, IdStrategy, IntentContext, Recipient, RecipientFormat, Transaction};
+pub use crypto::{
+    generate_keypair, generate_keypair_from_rng, generate_managed_keypair, keypair_from_seed,
+    sign_message, sign_message_deterministic, sign_message_eip191, verify_signature,
+    verify_signature_eip191, HashAlgorithm, ManagedSecretKey, MessageMode, PubkeyEncoding,
+    SignatureScheme,
+};
+pub use clock::{Clock, FixedClock, SystemClock};
 pub use error::MultisigError;
+pub use events::WalletEvent;
+pub use policy::Policy;
+pub use registry::WalletRegistry;
+pub use shared::SharedWallet;
+pub use verify::verify_threshold;
+pub use warnings::WalletWarning;
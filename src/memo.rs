@@ -0,0 +1,197 @@
+//! Transaction metadata encrypted to a set of signers
+//!
+//! ECIES over secp256k1: the memo is sealed once with ChaCha20-Poly1305
+//! under a random content key, and that key is wrapped for each recipient
+//! with a key derived from ECDH against one ephemeral keypair.
+//!
+//! | Bytes | Field                                                   |
+//! |-------|---------------------------------------------------------|
+//! | 1     | format version, currently `1`                           |
+//! | 33    | ephemeral compressed pubkey                             |
+//! | 2     | big-endian number of recipients `K`                     |
+//! | 81×K  | 33-byte recipient pubkey, 48-byte wrapped content key   |
+//! | 12    | memo nonce                                              |
+//! | rest  | sealed memo                                             |
+//!
+//! Store the bytes base64-encoded in `Transaction::metadata`, where the id
+//! and signatures cover them like any other metadata.
+
+use crate::crypto::generate_keypair;
+use crate::error::{MultisigError, Result};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use secp256k1::ecdh::SharedSecret;
+use secp256k1::{PublicKey, SecretKey};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+/// Current layout version
+pub const VERSION: u8 = 1;
+
+const PUBKEY_LEN: usize = 33;
+const WRAPPED_KEY_LEN: usize = 32 + 16;
+const NONCE_LEN: usize = 12;
+
+/// Encrypt `plaintext` so that any holder of one of `recipients`' secret
+/// keys can read it
+///
+/// Fails with `CryptoError` when there are no recipients or more than
+/// `u16::MAX`.
+pub fn encrypt_metadata(plaintext: &[u8], recipients: &[PublicKey]) -> Result<Vec<u8>> {
+    if recipients.is_empty() {
+        return Err(MultisigError::CryptoError(
+            "memo has no recipients".to_string(),
+        ));
+    }
+    let count = u16::try_from(recipients.len())
+        .map_err(|_| MultisigError::CryptoError("too many memo recipients".to_string()))?;
+
+    let (ephemeral_secret, ephemeral_public) = generate_keypair()?;
+    let content_key = Zeroizing::new(rand::random::<[u8; 32]>());
+
+    let mut bytes = vec![VERSION];
+    bytes.extend_from_slice(&ephemeral_public.serialize());
+    bytes.extend_from_slice(&count.to_be_bytes());
+    for recipient in recipients {
+        let kek = wrapping_key(
+            &SharedSecret::new(recipient, &ephemeral_secret),
+            &ephemeral_public,
+            recipient,
+        );
+        let wrapped = seal(&kek, &[0; NONCE_LEN], content_key.as_slice())?;
+        bytes.extend_from_slice(&recipient.serialize());
+        bytes.extend_from_slice(&wrapped);
+    }
+
+    let nonce = rand::random::<[u8; NONCE_LEN]>();
+    bytes.extend_from_slice(&nonce);
+    bytes.extend_from_slice(&seal(&content_key, &nonce, plaintext)?);
+    Ok(bytes)
+}
+
+/// Decrypt a memo produced by `encrypt_metadata`
+///
+/// Fails with `DecryptionFailed` when `secret_key` is not among the
+/// recipients or the bytes were altered.
+pub fn decrypt_metadata(ciphertext: &[u8], secret_key: &SecretKey) -> Result<Vec<u8>> {
+    let malformed = || MultisigError::CryptoError("malformed encrypted memo".to_string());
+
+    let (&version, rest) = ciphertext.split_first().ok_or_else(malformed)?;
+    if version != VERSION {
+        return Err(malformed());
+    }
+    let (ephemeral, rest) = split(rest, PUBKEY_LEN).ok_or_else(malformed)?;
+    let ephemeral_public = PublicKey::from_slice(ephemeral).map_err(|_| malformed())?;
+    let (count, mut rest) = split(rest, 2).ok_or_else(malformed)?;
+    let count = u16::from_be_bytes([count[0], count[1]]);
+
+    let own_public = secret_key.public_key(crate::crypto::context());
+    let own_encoded = own_public.serialize();
+    let mut wrapped_key = None;
+    for _ in 0..count {
+        let (entry, next) = split(rest, PUBKEY_LEN + WRAPPED_KEY_LEN).ok_or_else(malformed)?;
+        if entry[..PUBKEY_LEN] == own_encoded {
+            wrapped_key = Some(&entry[PUBKEY_LEN..]);
+        }
+        rest = next;
+    }
+    let (nonce, sealed) = split(rest, NONCE_LEN).ok_or_else(malformed)?;
+    let wrapped_key = wrapped_key.ok_or(MultisigError::DecryptionFailed)?;
+
+    let kek = wrapping_key(
+        &SharedSecret::new(&ephemeral_public, secret_key),
+        &ephemeral_public,
+        &own_public,
+    );
+    let content_key: Zeroizing<Vec<u8>> = Zeroizing::new(open(&kek, &[0; NONCE_LEN], wrapped_key)?);
+    let content_key: [u8; 32] = content_key.as_slice().try_into().map_err(|_| malformed())?;
+    open(&Zeroizing::new(content_key), nonce, sealed)
+}
+
+/// Per-recipient key for wrapping the content key
+///
+/// Binding both public keys keeps a wrapped key from being lifted into a
+/// memo for another ephemeral key or recipient.
+fn wrapping_key(
+    shared: &SharedSecret,
+    ephemeral: &PublicKey,
+    recipient: &PublicKey,
+) -> Zeroizing<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    hasher.update(b"multisig-rs/memo/v1");
+    hasher.update(shared.secret_bytes());
+    hasher.update(ephemeral.serialize());
+    hasher.update(recipient.serialize());
+    Zeroizing::new(hasher.finalize().into())
+}
+
+fn seal(key: &[u8; 32], nonce: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(nonce), plaintext)
+        .map_err(|_| MultisigError::CryptoError("memo encryption failed".to_string()))
+}
+
+fn open(key: &[u8; 32], nonce: &[u8], sealed: &[u8]) -> Result<Vec<u8>> {
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| MultisigError::DecryptionFailed)
+}
+
+fn split(bytes: &[u8], at: usize) -> Option<(&[u8], &[u8])> {
+    (bytes.len() >= at).then(|| bytes.split_at(at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::Transaction;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    #[test]
+    fn test_signers_can_decrypt() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (outsider, _) = generate_keypair().unwrap();
+
+        let sealed = encrypt_metadata(b"invoice 2024-117", &[pk1, pk2]).unwrap();
+        let tx = Transaction::new("recipient", 1000, Some(STANDARD.encode(&sealed)));
+
+        let stored = STANDARD.decode(tx.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            decrypt_metadata(&stored, &sk1).unwrap(),
+            b"invoice 2024-117"
+        );
+        assert_eq!(
+            decrypt_metadata(&stored, &sk2).unwrap(),
+            b"invoice 2024-117"
+        );
+        assert!(matches!(
+            decrypt_metadata(&stored, &outsider),
+            Err(MultisigError::DecryptionFailed)
+        ));
+
+        // Swapping in another ciphertext changes the id
+        let other = encrypt_metadata(b"invoice 2024-117", &[pk1, pk2]).unwrap();
+        let mut swapped = tx.clone();
+        swapped.metadata = Some(STANDARD.encode(&other));
+        assert!(!swapped.verify_id());
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut sealed = encrypt_metadata(b"memo", &[pk1]).unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(matches!(
+            decrypt_metadata(&sealed, &sk1),
+            Err(MultisigError::DecryptionFailed)
+        ));
+
+        assert!(matches!(
+            decrypt_metadata(&sealed[..10], &sk1),
+            Err(MultisigError::CryptoError(_))
+        ));
+        assert!(encrypt_metadata(b"memo", &[]).is_err());
+    }
+}
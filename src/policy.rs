@@ -0,0 +1,32 @@
+use secp256k1::PublicKey;
+
+/// Rule deciding when a transaction has been approved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Policy {
+    /// Any signers whose combined weight reaches the threshold
+    Threshold(usize),
+    /// The threshold must be met and every `mandatory` key must be among
+    /// the signers
+    Required {
+        mandatory: Vec<PublicKey>,
+        threshold: usize,
+    },
+}
+
+impl Policy {
+    /// The signature threshold the policy requires
+    pub fn threshold(&self) -> usize {
+        match self {
+            Policy::Threshold(threshold) => *threshold,
+            Policy::Required { threshold, .. } => *threshold,
+        }
+    }
+
+    /// Keys that must sign regardless of the threshold
+    pub fn mandatory(&self) -> &[PublicKey] {
+        match self {
+            Policy::Threshold(_) => &[],
+            Policy::Required { mandatory, .. } => mandatory,
+        }
+    }
+}
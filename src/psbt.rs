@@ -0,0 +1,192 @@
+//! PSBT-style container for signed transactions
+//!
+//! This is not BIP-174, just a stable binary layout for tooling:
+//!
+//! | Bytes | Field                                      |
+//! |-------|--------------------------------------------|
+//! | 1     | format version, currently `1`              |
+//! | 4     | big-endian length `L` of the JSON          |
+//! | L     | transaction JSON                           |
+//! | 4     | big-endian number of signatures `K`        |
+//! | 97×K  | 33-byte compressed pubkey, 64-byte compact ECDSA signature |
+//!
+//! Only ECDSA bundles can be encoded.
+
+use secp256k1::{ecdsa::Signature, PublicKey};
+use crate::error::{MultisigError, Result};
+use crate::transaction::Transaction;
+use crate::wallet::SignedTransaction;
+
+/// Current layout version
+pub const VERSION: u8 = 1;
+
+const PUBKEY_LEN: usize = 33;
+const SIGNATURE_LEN: usize = 64;
+
+/// Encoded partial transaction bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialTx(Vec<u8>);
+
+impl PartialTx {
+    /// Wrap bytes received from elsewhere; they are checked by `from_partial`
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        PartialTx(bytes)
+    }
+
+    /// The encoded bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Encode a signed bundle in the partial transaction layout
+///
+/// Fails with `SignatureSchemeMismatch` for Schnorr signatures and
+/// `InvalidPartialTransaction` when a key or signature is malformed.
+pub fn to_partial(bundle: &SignedTransaction) -> Result<PartialTx> {
+    let json = serde_json::to_vec(&bundle.transaction)?;
+
+    let mut bytes = vec![VERSION];
+    bytes.extend_from_slice(&length_prefix(json.len())?);
+    bytes.extend_from_slice(&json);
+    bytes.extend_from_slice(&length_prefix(bundle.signatures.len())?);
+
+    for (pk_hex, sig_hex) in &bundle.signatures {
+        if sig_hex.starts_with("schnorr:") {
+            return Err(MultisigError::SignatureSchemeMismatch);
+        }
+        let pubkey = hex::decode(pk_hex)
+            .ok()
+            .and_then(|b| PublicKey::from_slice(&b).ok())
+            .ok_or_else(|| malformed("invalid public key"))?;
+        let signature = hex::decode(sig_hex)
+            .ok()
+            .and_then(|b| Signature::from_compact(&b).ok())
+            .ok_or_else(|| malformed("invalid signature"))?;
+
+        bytes.extend_from_slice(&pubkey.serialize());
+        bytes.extend_from_slice(&signature.serialize_compact());
+    }
+
+    Ok(PartialTx(bytes))
+}
+
+/// Decode a partial transaction back into a signed bundle
+pub fn from_partial(partial: PartialTx) -> Result<SignedTransaction> {
+    let mut reader = Reader(partial.as_bytes());
+
+    let version = reader.take(1)?[0];
+    if version != VERSION {
+        return Err(malformed(&format!("unsupported version {}", version)));
+    }
+
+    let json_len = reader.read_u32()? as usize;
+    let transaction: Transaction = serde_json::from_slice(reader.take(json_len)?)?;
+
+    let count = reader.read_u32()? as usize;
+    let mut signatures = Vec::with_capacity(count.min(reader.0.len() / (PUBKEY_LEN + SIGNATURE_LEN)));
+    for _ in 0..count {
+        let pubkey = PublicKey::from_slice(reader.take(PUBKEY_LEN)?)
+            .map_err(|_| malformed("invalid public key"))?;
+        let signature = Signature::from_compact(reader.take(SIGNATURE_LEN)?)
+            .map_err(|_| malformed("invalid signature"))?;
+        signatures.push((
+            hex::encode(pubkey.serialize()),
+            hex::encode(signature.serialize_compact()),
+        ));
+    }
+
+    if !reader.0.is_empty() {
+        return Err(malformed("trailing bytes"));
+    }
+
+    Ok(SignedTransaction { transaction, signatures })
+}
+
+fn length_prefix(len: usize) -> Result<[u8; 4]> {
+    u32::try_from(len)
+        .map(u32::to_be_bytes)
+        .map_err(|_| malformed("field too large"))
+}
+
+fn malformed(reason: &str) -> MultisigError {
+    MultisigError::InvalidPartialTransaction(reason.to_string())
+}
+
+/// Cursor over the encoded bytes that fails cleanly on truncation
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(malformed("truncated input"));
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::wallet::MultisigWallet;
+
+    fn signed_bundle() -> (MultisigWallet, SignedTransaction) {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+
+        let bundle = wallet.finalize(&tx.id).unwrap();
+        (wallet, bundle)
+    }
+
+    #[test]
+    fn test_partial_roundtrip() {
+        let (wallet, bundle) = signed_bundle();
+
+        let partial = to_partial(&bundle).unwrap();
+        assert_eq!(partial.as_bytes()[0], VERSION);
+
+        let decoded = from_partial(partial).unwrap();
+        assert_eq!(decoded.transaction.id, bundle.transaction.id);
+        assert_eq!(decoded.signatures, bundle.signatures);
+        assert!(wallet.verify_signed_transaction(&decoded).unwrap());
+    }
+
+    #[test]
+    fn test_truncated_partial() {
+        let (_, bundle) = signed_bundle();
+        let bytes = to_partial(&bundle).unwrap().as_bytes().to_vec();
+
+        for len in [0, 1, 3, 10, bytes.len() - 1] {
+            let truncated = PartialTx::from_bytes(bytes[..len].to_vec());
+            assert!(from_partial(truncated).is_err(), "accepted {} bytes", len);
+        }
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(
+            from_partial(PartialTx::from_bytes(trailing)),
+            Err(MultisigError::InvalidPartialTransaction(_))
+        ));
+
+        let mut wrong_version = bytes;
+        wrong_version[0] = 9;
+        assert!(matches!(
+            from_partial(PartialTx::from_bytes(wrong_version)),
+            Err(MultisigError::InvalidPartialTransaction(_))
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use secp256k1::PublicKey;
+use crate::error::{MultisigError, Result};
+use crate::wallet::MultisigWallet;
+
+/// A set of independent multisig wallets keyed by account name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletRegistry {
+    accounts: HashMap<String, MultisigWallet>,
+}
+
+impl WalletRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an M-of-N wallet under a new account name
+    pub fn create_account(
+        &mut self,
+        name: &str,
+        threshold: usize,
+        authorized_keys: Vec<PublicKey>,
+    ) -> Result<&mut MultisigWallet> {
+        let wallet = MultisigWallet::new(threshold, authorized_keys)?;
+        self.insert_account(name, wallet)
+    }
+
+    /// Register an already configured wallet under a new account name
+    pub fn insert_account(
+        &mut self,
+        name: &str,
+        wallet: MultisigWallet,
+    ) -> Result<&mut MultisigWallet> {
+        if self.accounts.contains_key(name) {
+            return Err(MultisigError::AccountExists { name: name.to_string() });
+        }
+        Ok(self.accounts.entry(name.to_string()).or_insert(wallet))
+    }
+
+    /// Look up an account's wallet
+    pub fn get_account(&self, name: &str) -> Option<&MultisigWallet> {
+        self.accounts.get(name)
+    }
+
+    /// Look up an account's wallet for proposing, signing or executing
+    pub fn get_account_mut(&mut self, name: &str) -> Option<&mut MultisigWallet> {
+        self.accounts.get_mut(name)
+    }
+
+    /// Remove an account and hand back its wallet
+    pub fn remove_account(&mut self, name: &str) -> Result<MultisigWallet> {
+        self.accounts
+            .remove(name)
+            .ok_or_else(|| MultisigError::AccountNotFound { name: name.to_string() })
+    }
+
+    /// Account names in sorted order
+    pub fn account_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.accounts.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Number of unexecuted transactions over every account
+    pub fn total_pending_across_accounts(&self) -> usize {
+        self.accounts
+            .values()
+            .map(|wallet| wallet.pending_transactions().len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::transaction::Transaction;
+
+    #[test]
+    fn test_registry_accounts() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let mut registry = WalletRegistry::new();
+
+        registry.create_account("treasury", 2, vec![pk1, pk2]).unwrap();
+        registry.create_account("payroll", 1, vec![pk1]).unwrap();
+        assert!(matches!(
+            registry.create_account("payroll", 1, vec![pk2]),
+            Err(MultisigError::AccountExists { .. })
+        ));
+        assert_eq!(registry.account_names(), vec!["payroll", "treasury"]);
+
+        let treasury = registry.get_account_mut("treasury").unwrap();
+        treasury.propose_transaction(Transaction::new("a".to_string(), 1, None)).unwrap();
+        treasury.propose_transaction(Transaction::new("b".to_string(), 2, None)).unwrap();
+        let payroll = registry.get_account_mut("payroll").unwrap();
+        payroll.propose_transaction(Transaction::new("c".to_string(), 3, None)).unwrap();
+        assert_eq!(registry.total_pending_across_accounts(), 3);
+
+        registry.remove_account("payroll").unwrap();
+        assert!(registry.get_account("payroll").is_none());
+        assert!(matches!(
+            registry.remove_account("payroll"),
+            Err(MultisigError::AccountNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_registry_serialization() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let mut registry = WalletRegistry::new();
+        let wallet = registry.create_account("ops", 1, vec![pk1]).unwrap();
+        wallet.propose_transaction(Transaction::new("a".to_string(), 1, None)).unwrap();
+
+        let json = serde_json::to_string(&registry).unwrap();
+        let restored: WalletRegistry = serde_json::from_str(&json).unwrap();
+        let ops = restored.get_account("ops").unwrap();
+        assert_eq!(ops.pending_transactions().len(), 1);
+        assert_eq!(ops.wallet_id(), registry.get_account("ops").unwrap().wallet_id());
+    }
+}
@@ -0,0 +1,99 @@
+//! HTTP handlers for collecting signatures over a network
+//!
+//! `router` exposes a shared wallet:
+//!
+//! - `POST /propose` takes a `Transaction` and returns its `TransactionStatus`
+//! - `POST /sign/{tx_id}` takes a `SignRequest` and returns the new status
+//! - `GET /status/{tx_id}` returns the current status
+//!
+//! Errors are returned as `{"error": "..."}` with a matching status code.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use secp256k1::{ecdsa::Signature, PublicKey};
+use serde::{Deserialize, Serialize};
+use crate::error::MultisigError;
+use crate::shared::SharedWallet;
+use crate::transaction::Transaction;
+use crate::wallet::TransactionStatus;
+
+/// Body of `POST /sign/{tx_id}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignRequest {
+    /// Hex-encoded signer public key, compressed or uncompressed
+    pub pubkey: String,
+    /// Hex-encoded 64-byte compact ECDSA signature
+    pub signature: String,
+}
+
+/// Build the signature collection routes around a shared wallet
+pub fn router(wallet: SharedWallet) -> Router {
+    Router::new()
+        .route("/propose", post(propose))
+        .route("/sign/{tx_id}", post(sign))
+        .route("/status/{tx_id}", get(status))
+        .with_state(wallet)
+}
+
+async fn propose(
+    State(wallet): State<SharedWallet>,
+    Json(transaction): Json<Transaction>,
+) -> Result<(StatusCode, Json<TransactionStatus>), ApiError> {
+    let mut wallet = wallet.write();
+    let tx_id = transaction.id.clone();
+    wallet.propose_transaction(transaction)?;
+    Ok((StatusCode::CREATED, Json(wallet.transaction_status(&tx_id)?)))
+}
+
+async fn sign(
+    State(wallet): State<SharedWallet>,
+    Path(tx_id): Path<String>,
+    Json(request): Json<SignRequest>,
+) -> Result<Json<TransactionStatus>, ApiError> {
+    let pubkey_bytes = hex::decode(&request.pubkey)
+        .map_err(|_| MultisigError::InvalidPublicKey { index: None })?;
+    let pubkey = PublicKey::from_slice(&pubkey_bytes).map_err(MultisigError::from)?;
+    let signature_bytes =
+        hex::decode(&request.signature).map_err(|_| MultisigError::MalformedSignature)?;
+    let signature = Signature::from_compact(&signature_bytes).map_err(MultisigError::from)?;
+
+    let mut wallet = wallet.write();
+    wallet.add_signature(&tx_id, signature, &pubkey)?;
+    Ok(Json(wallet.transaction_status(&tx_id)?))
+}
+
+async fn status(
+    State(wallet): State<SharedWallet>,
+    Path(tx_id): Path<String>,
+) -> Result<Json<TransactionStatus>, ApiError> {
+    Ok(Json(wallet.transaction_status(&tx_id)?))
+}
+
+/// A wallet error rendered as an HTTP response
+#[derive(Debug)]
+pub struct ApiError(pub MultisigError);
+
+impl From<MultisigError> for ApiError {
+    fn from(error: MultisigError) -> Self {
+        ApiError(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self.0 {
+            MultisigError::TransactionNotFound { .. } => StatusCode::NOT_FOUND,
+            MultisigError::UnauthorizedSigner { .. } => StatusCode::FORBIDDEN,
+            MultisigError::DuplicateTransaction
+            | MultisigError::DuplicateNonce { .. }
+            | MultisigError::DuplicateSignature
+            | MultisigError::TransactionAlreadyExecuted => StatusCode::CONFLICT,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        let body = serde_json::json!({ "error": self.0.to_string() });
+        (status, Json(body)).into_response()
+    }
+}
@@ -0,0 +1,129 @@
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use secp256k1::{ecdsa::Signature, PublicKey};
+use crate::error::Result;
+use crate::transaction::Transaction;
+use crate::wallet::{MultisigWallet, TransactionStatus, WalletInfo, WalletInfoDetailed};
+
+/// A wallet that can be cloned and used from many threads
+///
+/// Queries take a read lock and mutations a write lock, each held only for
+/// the duration of the call. The lock is not reentrant: never call these
+/// methods while holding a guard from `read` or `write` on the same wallet,
+/// and take a guard instead when several steps must happen atomically.
+/// When locking several wallets at once, lock them in `wallet_id` order.
+#[derive(Debug, Clone)]
+pub struct SharedWallet(Arc<RwLock<MultisigWallet>>);
+
+impl SharedWallet {
+    /// Wrap a wallet for shared use
+    pub fn new(wallet: MultisigWallet) -> Self {
+        SharedWallet(Arc::new(RwLock::new(wallet)))
+    }
+
+    /// Lock the wallet for reading
+    ///
+    /// Every wallet method leaves it consistent, so a panic in another thread
+    /// does not make it unusable.
+    pub fn read(&self) -> RwLockReadGuard<'_, MultisigWallet> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Lock the wallet for writing
+    pub fn write(&self) -> RwLockWriteGuard<'_, MultisigWallet> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Propose a transaction under a write lock
+    pub fn propose_transaction(&self, transaction: Transaction) -> Result<()> {
+        self.write().propose_transaction(transaction)
+    }
+
+    /// Add a signature under a write lock
+    pub fn add_signature(
+        &self,
+        tx_id: &str,
+        signature: Signature,
+        signer_pubkey: &PublicKey,
+    ) -> Result<()> {
+        self.write().add_signature(tx_id, signature, signer_pubkey)
+    }
+
+    /// Execute a transaction under a write lock
+    pub fn execute_transaction(&self, tx_id: &str) -> Result<Transaction> {
+        self.write().execute_transaction(tx_id)
+    }
+
+    /// Check the signature policy under a read lock
+    pub fn has_enough_signatures(&self, tx_id: &str) -> Result<bool> {
+        self.read().has_enough_signatures(tx_id)
+    }
+
+    /// Report a proposal's progress under a read lock
+    pub fn transaction_status(&self, tx_id: &str) -> Result<TransactionStatus> {
+        self.read().transaction_status(tx_id)
+    }
+
+    /// Get wallet information under a read lock
+    pub fn info(&self) -> WalletInfo {
+        self.read().info()
+    }
+
+    /// Get wallet information with readiness counts under a read lock
+    pub fn info_detailed(&self) -> WalletInfoDetailed {
+        self.read().info_detailed()
+    }
+}
+
+impl From<MultisigWallet> for SharedWallet {
+    fn from(wallet: MultisigWallet) -> Self {
+        SharedWallet::new(wallet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use crate::crypto::generate_keypair;
+
+    #[test]
+    fn test_concurrent_signatures() {
+        let keys: Vec<_> = (0..8).map(|_| generate_keypair().unwrap()).collect();
+        let pubkeys = keys.iter().map(|(_, pk)| *pk).collect();
+        let shared = SharedWallet::new(MultisigWallet::new(8, pubkeys).unwrap());
+        let wallet_id = shared.read().wallet_id().to_string();
+
+        let transactions: Vec<Transaction> = (0..4)
+            .map(|i| Transaction::new("recipient".to_string(), 100 + i, None))
+            .collect();
+        for tx in &transactions {
+            shared.propose_transaction(tx.clone()).unwrap();
+        }
+
+        let handles: Vec<_> = keys
+            .into_iter()
+            .map(|(sk, pk)| {
+                let shared = shared.clone();
+                let transactions = transactions.clone();
+                let wallet_id = wallet_id.clone();
+                thread::spawn(move || {
+                    for tx in &transactions {
+                        let signature = tx.sign(&sk, &wallet_id).unwrap();
+                        shared.add_signature(&tx.id, signature, &pk).unwrap();
+                        shared.has_enough_signatures(&tx.id).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for tx in &transactions {
+            assert!(shared.has_enough_signatures(&tx.id).unwrap());
+            assert_eq!(shared.transaction_status(&tx.id).unwrap().signatures_collected, 8);
+            shared.execute_transaction(&tx.id).unwrap();
+        }
+        assert_eq!(shared.info().total_signers, 8);
+    }
+}
@@ -1,77 +1,621 @@
-use serde::{Deserialize, Serialize};
-use secp256k1::{PublicKey, SecretKey, ecdsa::Signature};
-use crate::crypto::{sign_message, hash_message};
-use crate::error::Result;
+use std::fmt;
+use std::io::Read;
+use std::str::FromStr;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use secp256k1::{Keypair, PublicKey, SecretKey, ecdsa::{RecoverableSignature, Signature}, schnorr};
+use crate::crypto::{
+    hash_message, sign_message, sign_message_eip191, sign_message_recoverable,
+    sign_message_schnorr, sign_message_with, HashAlgorithm,
+};
+use crate::error::{MultisigError, Result};
 
-/// Represents a transaction in the multisig wallet
+/// Accepted shape of a transaction recipient
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RecipientFormat {
+    /// Any non-empty string
+    #[default]
+    Raw,
+    /// A hex-encoded secp256k1 public key
+    HexPubkey,
+}
+
+/// Destination of a transaction
+///
+/// Serializes with an explicit variant tag, but a bare JSON string, as
+/// written before this type existed, still loads as `RawAddress`. Serde is
+/// derived through `remote = "Self"` so the impls below can accept both.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(remote = "Self")]
+pub enum Recipient {
+    /// A hex-encoded secp256k1 public key
+    PublicKeyHex(String),
+    /// Any other address, used verbatim
+    RawAddress(String),
+}
+
+impl Recipient {
+    /// The address as given, without canonicalization
+    pub fn as_str(&self) -> &str {
+        match self {
+            Recipient::PublicKeyHex(s) | Recipient::RawAddress(s) => s,
+        }
+    }
+}
+
+/// Canonical form used in ids and signing hashes: public keys in lowercase
+/// hex, raw addresses unchanged
+///
+/// The two variants can print the same; `signing_hash` marks which one it
+/// is, so they never share an id or a signature.
+impl fmt::Display for Recipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recipient::PublicKeyHex(hex) => f.write_str(&hex.to_ascii_lowercase()),
+            Recipient::RawAddress(address) => f.write_str(address),
+        }
+    }
+}
+
+/// Parse a recipient, recognizing hex-encoded public keys
+///
+/// Unlike `From<String>`, which always gives a `RawAddress`.
+impl FromStr for Recipient {
+    type Err = MultisigError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.is_empty() {
+            return Err(MultisigError::InvalidRecipient);
+        }
+        let is_pubkey = hex::decode(s)
+            .ok()
+            .is_some_and(|bytes| PublicKey::from_slice(&bytes).is_ok());
+        Ok(if is_pubkey {
+            Recipient::PublicKeyHex(s.to_ascii_lowercase())
+        } else {
+            Recipient::RawAddress(s.to_string())
+        })
+    }
+}
+
+impl From<String> for Recipient {
+    fn from(address: String) -> Self {
+        Recipient::RawAddress(address)
+    }
+}
+
+impl From<&str> for Recipient {
+    fn from(address: &str) -> Self {
+        Recipient::RawAddress(address.to_string())
+    }
+}
+
+impl Serialize for Recipient {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        Recipient::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Recipient {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        // Untagged matching needs a self-describing format; binary formats
+        // never held the bare-string form anyway
+        if !deserializer.is_human_readable() {
+            return Recipient::deserialize(deserializer);
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Tagged(#[serde(with = "Recipient")] Recipient),
+            Bare(String),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Tagged(recipient) => recipient,
+            Repr::Bare(address) => Recipient::RawAddress(address),
+        })
+    }
+}
 
+/// Represents a transaction in the multisig wallet
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
     pub id: String,
-    pub recipient: String,
-    pub amount: u64,
+    pub recipient: Recipient,
+
+    /// Amount in the smallest unit of the asset
+    ///
+    /// Migration note: this used to be a `u64`. Ids and signing hashes of
+    /// amounts that fit in a `u64` are unchanged and stored JSON loads as
+    /// before; callers passing `u64` variables need `u128::from`.
+    pub amount: u128,
+
+    /// Fee paid on top of `amount`
+    #[serde(default)]
+    pub fee: u64,
     pub metadata: Option<String>,
     pub timestamp: u64,
     pub nonce: u64,
+
+    /// Unix time (seconds) after which the transaction can no longer be
+    /// signed or executed
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+
+    /// Unix time (seconds) before which the transaction cannot be executed,
+    /// though it may already collect signatures
+    #[serde(default)]
+    pub not_before: Option<u64>,
+
+    /// Free-form categories such as `payroll` or `refund`, covered by
+    /// signatures so they cannot be changed after signing
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// SHA-256 of a document stored outside the transaction, from
+    /// `hash_streaming`, so large attachments are bound by the id and
+    /// signatures without being carried around
+    #[serde(default)]
+    pub metadata_hash: Option<[u8; 32]>,
+}
+
+/// How transaction ids are assigned
+///
+/// The default, `HashId`, derives the id from the contents so it can be
+/// checked by anyone. Other strategies (UUIDs, ids from an external system,
+/// counters) only have to produce ids that are unique within a wallet;
+/// the wallet rejects duplicates whatever the strategy.
+pub trait IdStrategy: Send + Sync {
+    /// Id for a newly built transaction
+    fn compute_id(&self, tx: &Transaction) -> String;
+
+    /// Whether `tx.id` is acceptable for the contents of `tx`
+    ///
+    /// Strategies whose ids do not depend on the contents cannot check more
+    /// than that an id is present.
+    fn is_valid_id(&self, tx: &Transaction) -> bool {
+        !tx.id.is_empty()
+    }
+}
+
+/// Content-hash ids, as computed by `Transaction::new`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashId;
+
+impl IdStrategy for HashId {
+    fn compute_id(&self, tx: &Transaction) -> String {
+        tx.calculate_id()
+    }
+
+    fn is_valid_id(&self, tx: &Transaction) -> bool {
+        tx.verify_id()
+    }
+}
+
+/// Execution context a transaction is signed for, beyond its contents
+///
+/// Signing the context alongside the transaction keeps a signature made for
+/// one chain from being replayed on another.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IntentContext {
+    pub chain_id: u64,
+    pub wallet_id: String,
+}
+
+/// Transactions are identified by `id`: two values with the same id are equal
+/// and hash alike, whatever their other fields say.
+impl PartialEq for Transaction {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Transaction {}
+
+impl std::hash::Hash for Transaction {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Ordered by creation `timestamp`, oldest first, then by `id`
+///
+/// This ordering is part of the API and will not change. It agrees with
+/// `Eq` for transactions whose id matches their contents, since the id
+/// covers the timestamp.
+impl Ord for Transaction {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.timestamp, &self.id).cmp(&(other.timestamp, &other.id))
+    }
+}
+
+impl PartialOrd for Transaction {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Current Unix time in seconds
+pub(crate) fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
 }
 
 impl Transaction {
-    /// Create a new transaction
+    /// Create a new transaction with a random nonce
+    pub fn new(recipient: impl Into<Recipient>, amount: u128, metadata: Option<String>) -> Self {
+        Self::with_nonce(recipient, amount, metadata, rand::random::<u64>())
+    }
 
-    pub fn new(recipient: String, amount: u64, metadata: Option<String>) -> Self {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        let nonce = rand::random::<u64>();
+    /// Create a new transaction with a random nonce and an id from `strategy`
+    ///
+    /// The `with_*` builders recompute a content-hash id, so apply them
+    /// before this, not after.
+    pub fn new_with_id_strategy(
+        recipient: impl Into<Recipient>,
+        amount: u128,
+        metadata: Option<String>,
+        strategy: &dyn IdStrategy,
+    ) -> Self {
+        let mut tx = Self::new(recipient, amount, metadata);
+        tx.id = strategy.compute_id(&tx);
+        tx
+    }
+
+    /// Create a new transaction carrying a fee, with a random nonce
+    pub fn new_with_fee(
+        recipient: impl Into<Recipient>,
+        amount: u128,
+        fee: u64,
+        metadata: Option<String>,
+    ) -> Self {
+        let mut tx = Self::new(recipient, amount, metadata);
+        tx.fee = fee;
+        tx.id = tx.calculate_id();
+        tx
+    }
+
+    /// Create a new transaction with an explicit nonce
+    ///
+    /// Use `MultisigWallet::next_nonce` to get a monotonic per-recipient
+    /// counter instead of a random value.
+    pub fn with_nonce(
+        recipient: impl Into<Recipient>,
+        amount: u128,
+        metadata: Option<String>,
+        nonce: u64,
+    ) -> Self {
+        let timestamp = current_timestamp();
         
         let mut tx = Transaction {
             id: String::new(),
-            recipient,
+            recipient: recipient.into(),
             amount,
+            fee: 0,
             metadata,
             timestamp,
             nonce,
+            expires_at: None,
+            not_before: None,
+            tags: Vec::new(),
+            metadata_hash: None,
         };
         
         // Generate transaction ID
         tx.id = tx.calculate_id();
         tx
     }
+
+    /// Set the creation time, for transactions built against a wallet clock
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self.id = self.calculate_id();
+        self
+    }
+
+    /// Set the Unix time after which the transaction expires
+    pub fn with_expiry(mut self, expires_at: u64) -> Self {
+        self.expires_at = Some(expires_at);
+        self.id = self.calculate_id();
+        self
+    }
+
+    /// Set the Unix time before which the transaction cannot be executed
+    pub fn with_not_before(mut self, not_before: u64) -> Self {
+        self.not_before = Some(not_before);
+        self.id = self.calculate_id();
+        self
+    }
+
+    /// Attach category tags to the transaction
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self.id = self.calculate_id();
+        self
+    }
+
+    /// Bind an externally stored document by its `hash_streaming` digest
+    pub fn with_metadata_hash(mut self, metadata_hash: [u8; 32]) -> Self {
+        self.metadata_hash = Some(metadata_hash);
+        self.id = self.calculate_id();
+        self
+    }
+
+    /// SHA-256 of a document read to the end in fixed-size chunks
+    ///
+    /// Memory use does not grow with the document, so this suits documents
+    /// too large to keep in `metadata`.
+    pub fn hash_streaming(mut reader: impl Read) -> Result<[u8; 32]> {
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut reader, &mut hasher)?;
+        Ok(hasher.finalize().into())
+    }
+
+    /// Check a document against `metadata_hash`
+    ///
+    /// False when the transaction references no document.
+    pub fn matches_metadata_document(&self, reader: impl Read) -> Result<bool> {
+        match self.metadata_hash {
+            Some(expected) => Ok(Self::hash_streaming(reader)? == expected),
+            None => Ok(false),
+        }
+    }
+
+    /// Check whether the transaction carries the given tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Amount plus fee, or `AmountOverflow` if that does not fit in a `u128`
+    pub fn total_cost(&self) -> Result<u128> {
+        self.amount
+            .checked_add(u128::from(self.fee))
+            .ok_or(MultisigError::AmountOverflow)
+    }
+
+    /// Check that `id` matches the transaction contents
+    pub fn verify_id(&self) -> bool {
+        self.id == self.calculate_id()
+    }
+
+    /// Check that the recipient is present and matches the expected format
+    pub fn validate(&self, format: RecipientFormat) -> Result<()> {
+        if self.recipient.as_str().is_empty() {
+            return Err(MultisigError::InvalidRecipient);
+        }
+
+        if format == RecipientFormat::HexPubkey {
+            let bytes = hex::decode(self.recipient.as_str())
+                .map_err(|_| MultisigError::InvalidRecipient)?;
+            PublicKey::from_slice(&bytes).map_err(|_| MultisigError::InvalidRecipient)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check whether the transaction has expired at the given Unix time
+    pub fn is_expired_at(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now > expires_at)
+    }
+
+    /// Check whether the time lock has opened at the given Unix time
+    pub fn is_unlocked_at(&self, now: u64) -> bool {
+        self.not_before.is_none_or(|not_before| now >= not_before)
+    }
     
     /// Calculate the transaction ID (hash of transaction data)
-
-    fn calculate_id(&self) -> String {
-        let data = format!(
-            "{}:{}:{}:{}:{}",
+    pub(crate) fn calculate_id(&self) -> String {
+        let mut data = format!(
+            "{}:{}:{}:{}:{}:{}",
             self.recipient,
             self.amount,
             self.metadata.as_deref().unwrap_or(""),
             self.timestamp,
-            self.nonce
+            self.nonce,
+            self.expires_at.map(|t| t.to_string()).unwrap_or_default()
         );
+
+        // Transactions to a raw address without a time lock, fee, document
+        // hash or tags keep the ids they had before those fields existed. The
+        // rest hash the length-prefixed `signing_hash` encoding, so an
+        // extension can never be spelled as another one or as a tag, and a
+        // public key recipient never as a raw address.
+        let extended = matches!(self.recipient, Recipient::PublicKeyHex(_))
+            || self.not_before.is_some()
+            || self.fee != 0
+            || self.metadata_hash.is_some()
+            || !self.tags.is_empty();
+        if extended {
+            data.push_str(":ext:");
+            data.push_str(&hex::encode(self.signing_hash()));
+        }
         hex::encode(hash_message(data.as_bytes()))
     }
     
-    /// Serialize the transaction for signing
-
+    /// Serialize the transaction as JSON for storage and transport
+    ///
+    /// JSON field order and formatting are not guaranteed to stay stable
+    /// across serde versions, so these bytes must never be used as the signed
+    /// payload. Signatures cover `signing_hash` instead.
     pub fn to_bytes(&self) -> Vec<u8> {
         serde_json::to_vec(self).unwrap()
     }
+
+    /// Parse bytes produced by `to_bytes`, as received from an untrusted peer
+    ///
+    /// Malformed JSON fails with `SerializationError`, an `id` that does not
+    /// match the contents with `TransactionIdMismatch` and an empty recipient
+    /// with `InvalidRecipient`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Transaction> {
+        let transaction: Transaction = serde_json::from_slice(bytes)?;
+        if !transaction.verify_id() {
+            return Err(MultisigError::TransactionIdMismatch);
+        }
+        transaction.validate(RecipientFormat::Raw)?;
+        Ok(transaction)
+    }
+
+    /// Encode the JSON form of the transaction as base64 for sharing
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.to_bytes())
+    }
+
+    /// Decode a transaction produced by `to_base64`
+    pub fn from_base64(s: &str) -> Result<Transaction> {
+        let bytes = STANDARD.decode(s.trim())?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Hash of the transaction contents that signatures commit to
+    ///
+    /// Fields are written in a fixed order, each length-prefixed or
+    /// fixed-width, so the encoding is unambiguous and independent of serde.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        fn put_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+            buf.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+            buf.extend_from_slice(bytes);
+        }
+
+        fn put_option(buf: &mut Vec<u8>, value: Option<&[u8]>) {
+            match value {
+                Some(bytes) => {
+                    buf.push(1);
+                    put_bytes(buf, bytes);
+                }
+                None => buf.push(0),
+            }
+        }
+
+        let mut buf = Vec::new();
+        put_bytes(&mut buf, b"multisig-rs/transaction/v1");
+        put_bytes(&mut buf, self.recipient.to_string().as_bytes());
+        // Only the low 64 bits go here; larger amounts add an extension below
+        buf.extend_from_slice(&(self.amount as u64).to_be_bytes());
+        put_option(&mut buf, self.metadata.as_deref().map(str::as_bytes));
+        buf.extend_from_slice(&self.timestamp.to_be_bytes());
+        buf.extend_from_slice(&self.nonce.to_be_bytes());
+        put_option(&mut buf, self.expires_at.map(u64::to_be_bytes).as_ref().map(|b| &b[..]));
+
+        // Optional extensions are appended only when present, so transactions
+        // without them hash exactly as they did before. A public key
+        // recipient is marked with `P`, the time lock with `N`, the high half
+        // of the amount with `A`, the fee with `F` and the document hash with
+        // `M`; a tag count always starts with a zero byte.
+        if matches!(self.recipient, Recipient::PublicKeyHex(_)) {
+            buf.push(b'P');
+        }
+        if let Some(not_before) = self.not_before {
+            buf.push(b'N');
+            buf.extend_from_slice(&not_before.to_be_bytes());
+        }
+        let amount_high = (self.amount >> 64) as u64;
+        if amount_high != 0 {
+            buf.push(b'A');
+            buf.extend_from_slice(&amount_high.to_be_bytes());
+        }
+        if self.fee != 0 {
+            buf.push(b'F');
+            buf.extend_from_slice(&self.fee.to_be_bytes());
+        }
+        if let Some(metadata_hash) = &self.metadata_hash {
+            buf.push(b'M');
+            buf.extend_from_slice(metadata_hash);
+        }
+        if !self.tags.is_empty() {
+            buf.extend_from_slice(&(self.tags.len() as u64).to_be_bytes());
+            for tag in &self.tags {
+                put_bytes(&mut buf, tag.as_bytes());
+            }
+        }
+
+        hash_message(&buf)
+    }
     
-    /// Sign the transaction with a private key
+    /// Build the message signers sign for a specific wallet
+    ///
+    /// Binding the wallet id into the payload means a signature collected for
+    /// one wallet cannot be replayed into another that shares signers.
+    pub fn signing_payload(&self, wallet_id: &str) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(wallet_id.as_bytes());
+        payload.push(b':');
+        payload.extend_from_slice(&self.signing_hash());
+        payload
+    }
+
+    /// Build the message signers sign for a wallet bound to a chain
+    ///
+    /// Like `signing_payload`, with the chain id added. The leading tag
+    /// keeps it distinct from every payload without a context.
+    pub fn signing_payload_with_context(&self, context: &IntentContext) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(b"multisig-rs/intent/v1:");
+        payload.extend_from_slice(context.wallet_id.as_bytes());
+        payload.push(b':');
+        payload.extend_from_slice(&context.chain_id.to_be_bytes());
+        payload.extend_from_slice(&self.signing_hash());
+        payload
+    }
 
-    pub fn sign(&self, secret_key: &SecretKey) -> Result<Signature> {
-        let message = self.to_bytes();
+    /// Sign the transaction for a wallet bound to a chain
+    pub fn sign_with_context(
+        &self,
+        secret_key: &SecretKey,
+        context: &IntentContext,
+    ) -> Result<Signature> {
+        sign_message(&self.signing_payload_with_context(context), secret_key)
+    }
+    
+    /// Sign the transaction with a private key for the given wallet
+    pub fn sign(&self, secret_key: &SecretKey, wallet_id: &str) -> Result<Signature> {
+        let message = self.signing_payload(wallet_id);
         sign_message(&message, secret_key)
     }
+
+    /// Sign the transaction for a wallet that uses a non-default hash
+    /// algorithm
+    pub fn sign_with_hash(
+        &self,
+        secret_key: &SecretKey,
+        wallet_id: &str,
+        algorithm: HashAlgorithm,
+    ) -> Result<Signature> {
+        let message = self.signing_payload(wallet_id);
+        sign_message_with(algorithm, &message, secret_key)
+    }
+
+    /// Sign the transaction for a wallet in `MessageMode::Eip191`
+    pub fn sign_eip191(&self, secret_key: &SecretKey, wallet_id: &str) -> Result<Signature> {
+        sign_message_eip191(&self.signing_payload(wallet_id), secret_key)
+    }
+
+    /// Sign the transaction with a recoverable ECDSA signature for the given
+    /// wallet
+    pub fn sign_recoverable(
+        &self,
+        secret_key: &SecretKey,
+        wallet_id: &str,
+    ) -> Result<RecoverableSignature> {
+        let message = self.signing_payload(wallet_id);
+        sign_message_recoverable(&message, secret_key)
+    }
+
+    /// Sign the transaction with a BIP-340 Schnorr signature for the given
+    /// wallet
+    pub fn sign_schnorr(&self, keypair: &Keypair, wallet_id: &str) -> Result<schnorr::Signature> {
+        let message = self.signing_payload(wallet_id);
+        sign_message_schnorr(&message, keypair)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::generate_keypair;
+    use crate::crypto::{generate_keypair, verify_signature};
 
     #[test]
     fn test_transaction_creation() {
@@ -94,7 +638,7 @@ mod tests {
             None,
         );
         
-        let signature = tx.sign(&secret_key);
+        let signature = tx.sign(&secret_key, "wallet");
         assert!(signature.is_ok());
     }
 
@@ -113,4 +657,315 @@ mod tests {
         assert_eq!(tx.id, deserialized.id);
         assert_eq!(tx.amount, deserialized.amount);
     }
+
+    #[test]
+    fn test_transaction_expiry() {
+        let tx = Transaction::new("recipient_address".to_string(), 1000, None);
+        let expiring = tx.clone().with_expiry(2_000);
+
+        assert_ne!(tx.id, expiring.id);
+        assert!(!tx.is_expired_at(u64::MAX));
+        assert!(!expiring.is_expired_at(2_000));
+        assert!(expiring.is_expired_at(2_001));
+    }
+
+    #[test]
+    fn test_recipient_validation() {
+        let (_, public_key) = generate_keypair().unwrap();
+
+        let empty = Transaction::new(String::new(), 1000, None);
+        assert!(matches!(empty.validate(RecipientFormat::Raw), Err(MultisigError::InvalidRecipient)));
+
+        let raw = Transaction::new("recipient_address".to_string(), 1000, None);
+        assert!(raw.validate(RecipientFormat::Raw).is_ok());
+        assert!(matches!(raw.validate(RecipientFormat::HexPubkey), Err(MultisigError::InvalidRecipient)));
+
+        let pubkey = Transaction::new(hex::encode(public_key.serialize()), 1000, None);
+        assert!(pubkey.validate(RecipientFormat::HexPubkey).is_ok());
+    }
+
+    #[test]
+    fn test_signing_hash_is_pinned() {
+        let tx = Transaction {
+            id: "fixed".to_string(),
+            recipient: "recipient_address".into(),
+            amount: 1000,
+            fee: 0,
+            metadata: Some("Pinned".to_string()),
+            timestamp: 1_700_000_000,
+            nonce: 42,
+            expires_at: None,
+            not_before: None,
+            tags: Vec::new(),
+            metadata_hash: None,
+        };
+
+        assert_eq!(
+            hex::encode(tx.signing_hash()),
+            "747200ac49566d0a065e97a33f243b071a6f39af1543e6ffec7fef441d068425"
+        );
+
+        // Changing serialization details leaves the hash untouched, changing
+        // content does not
+        let round_tripped: Transaction = serde_json::from_slice(&tx.to_bytes()).unwrap();
+        assert_eq!(round_tripped.signing_hash(), tx.signing_hash());
+        let mut altered = tx.clone();
+        altered.amount = 1001;
+        assert_ne!(altered.signing_hash(), tx.signing_hash());
+    }
+
+    #[test]
+    fn test_base64_roundtrip() {
+        let tx = Transaction::new("recipient".to_string(), 1000, Some("Share".to_string()))
+            .with_expiry(current_timestamp() + 60);
+
+        let decoded = Transaction::from_base64(&tx.to_base64()).unwrap();
+        assert_eq!(decoded.id, tx.id);
+        assert_eq!(decoded.signing_hash(), tx.signing_hash());
+
+        assert!(matches!(
+            Transaction::from_base64("not base64!"),
+            Err(MultisigError::Base64Error(_))
+        ));
+    }
+
+    #[test]
+    fn test_tags_are_signed() {
+        let (secret_key, public_key) = generate_keypair().unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None)
+            .with_tags(vec!["payroll".to_string()]);
+        assert!(tx.has_tag("payroll"));
+
+        let signature = tx.sign(&secret_key, "wallet").unwrap();
+
+        let mut retagged = tx.clone();
+        retagged.tags = vec!["refund".to_string()];
+        assert_ne!(retagged.signing_hash(), tx.signing_hash());
+        assert!(!crate::crypto::verify_signature(
+            &retagged.signing_payload("wallet"),
+            &signature,
+            &public_key
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_time_lock() {
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        let locked = tx.clone().with_not_before(2_000);
+
+        assert!(tx.is_unlocked_at(0));
+        assert!(!locked.is_unlocked_at(1_999));
+        assert!(locked.is_unlocked_at(2_000));
+        assert!(locked.is_unlocked_at(2_001));
+
+        assert_ne!(locked.id, tx.id);
+        assert_ne!(locked.signing_hash(), tx.signing_hash());
+    }
+
+    #[test]
+    fn test_amounts_above_u64() {
+        let small = Transaction::with_nonce("recipient".to_string(), 5, None, 1);
+        let mut large = small.clone();
+        large.amount = (1u128 << 64) + 5;
+
+        // The low 64 bits match, so the extension is what tells them apart
+        assert_ne!(large.signing_hash(), small.signing_hash());
+
+        let decoded: Transaction = serde_json::from_slice(&large.to_bytes()).unwrap();
+        assert_eq!(decoded.amount, large.amount);
+        assert_eq!(decoded.signing_hash(), large.signing_hash());
+    }
+
+    #[test]
+    fn test_from_bytes_checks_invariants() {
+        let tx = Transaction::new("recipient".to_string(), 1000, None)
+            .with_tags(vec!["ops".to_string()]);
+        let decoded = Transaction::from_bytes(&tx.to_bytes()).unwrap();
+        assert_eq!(decoded.signing_hash(), tx.signing_hash());
+
+        assert!(matches!(
+            Transaction::from_bytes(b"{not json"),
+            Err(MultisigError::SerializationError(_))
+        ));
+
+        let mut forged = tx.clone();
+        forged.amount = 1;
+        assert!(matches!(
+            Transaction::from_bytes(&forged.to_bytes()),
+            Err(MultisigError::TransactionIdMismatch)
+        ));
+
+        let mut empty = tx;
+        empty.recipient = "".into();
+        empty.id = empty.calculate_id();
+        assert!(matches!(
+            Transaction::from_bytes(&empty.to_bytes()),
+            Err(MultisigError::InvalidRecipient)
+        ));
+    }
+
+    #[test]
+    fn test_fee_is_authenticated() {
+        let (secret_key, public_key) = generate_keypair().unwrap();
+        let tx = Transaction::new_with_fee("recipient".to_string(), 1000, 25, None);
+        assert_eq!(tx.total_cost().unwrap(), 1025);
+        assert!(tx.verify_id());
+
+        let signature = tx.sign(&secret_key, "wallet").unwrap();
+        let mut altered = tx.clone();
+        altered.fee = 1;
+        assert_ne!(altered.calculate_id(), tx.id);
+        let payload = altered.signing_payload("wallet");
+        assert!(!verify_signature(&payload, &signature, &public_key).unwrap());
+
+        let overflowing = Transaction::new_with_fee("recipient".to_string(), u128::MAX, 1, None);
+        assert!(matches!(overflowing.total_cost(), Err(MultisigError::AmountOverflow)));
+    }
+
+    #[test]
+    fn test_recipient_legacy_json() {
+        let tx = Transaction::with_nonce("alice", 1000, None, 7);
+        let json = serde_json::to_string(&tx).unwrap();
+        assert!(json.contains(r#""recipient":{"RawAddress":"alice"}"#));
+
+        // Transactions stored while the recipient was a plain string
+        let legacy = json.replace(r#"{"RawAddress":"alice"}"#, r#""alice""#);
+        let decoded = Transaction::from_bytes(legacy.as_bytes()).unwrap();
+        assert_eq!(decoded.recipient, Recipient::RawAddress("alice".to_string()));
+        assert_eq!(decoded.id, tx.id);
+        assert_eq!(decoded.signing_hash(), tx.signing_hash());
+    }
+
+    #[test]
+    fn test_recipient_parsing_is_canonical() {
+        let (_, public_key) = generate_keypair().unwrap();
+        let pubkey_hex = hex::encode(public_key.serialize());
+
+        let parsed: Recipient = pubkey_hex.to_uppercase().parse().unwrap();
+        assert_eq!(parsed, Recipient::PublicKeyHex(pubkey_hex.clone()));
+        assert_eq!(parsed.to_string(), pubkey_hex);
+        assert_eq!("alice".parse::<Recipient>().unwrap(), Recipient::RawAddress("alice".into()));
+        assert!(matches!("".parse::<Recipient>(), Err(MultisigError::InvalidRecipient)));
+
+        // Ids depend on the canonical form, not on how the key was written
+        let lower = Transaction::with_nonce(parsed.clone(), 1000, None, 1).with_timestamp(1);
+        let upper = Transaction::with_nonce(
+            Recipient::PublicKeyHex(pubkey_hex.to_uppercase()),
+            1000,
+            None,
+            1,
+        )
+        .with_timestamp(1);
+        assert_eq!(lower.id, upper.id);
+
+        let decoded: Transaction = serde_json::from_slice(&lower.to_bytes()).unwrap();
+        assert_eq!(decoded.recipient, parsed);
+    }
+
+    #[test]
+    fn test_recipient_variant_is_authenticated() {
+        let (sk, public_key) = generate_keypair().unwrap();
+        let pubkey_hex = hex::encode(public_key.serialize());
+
+        let raw = Transaction::with_nonce(Recipient::RawAddress(pubkey_hex.clone()), 1000, None, 1)
+            .with_timestamp(1);
+        let key = Transaction::with_nonce(
+            Recipient::PublicKeyHex(pubkey_hex.to_uppercase()),
+            1000,
+            None,
+            1,
+        )
+        .with_timestamp(1);
+        assert_eq!(raw.recipient.to_string(), key.recipient.to_string());
+        assert_ne!(raw.signing_hash(), key.signing_hash());
+        assert_ne!(raw.id, key.id);
+
+        let signature = raw.sign(&sk, "wallet").unwrap();
+        let verify = |tx: &Transaction| {
+            verify_signature(&tx.signing_payload("wallet"), &signature, &public_key).unwrap()
+        };
+        assert!(verify(&raw));
+        assert!(!verify(&key));
+    }
+
+    #[test]
+    fn test_ordering_and_identity() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let older = Transaction::with_nonce("recipient", 1000, None, 1).with_timestamp(100);
+        let newer = Transaction::with_nonce("recipient", 1000, None, 2).with_timestamp(200);
+        let also_newer = Transaction::with_nonce("recipient", 1000, None, 3).with_timestamp(200);
+
+        let ordered: Vec<_> = [&also_newer, &newer, &older]
+            .into_iter()
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        assert_eq!(ordered[0].id, older.id);
+        assert_eq!(ordered[1].id, newer.id.clone().min(also_newer.id.clone()));
+
+        let mut unique = HashSet::new();
+        assert!(unique.insert(older.clone()));
+        assert!(!unique.insert(older.clone()));
+        assert!(unique.insert(newer));
+        assert_eq!(unique.len(), 2);
+    }
+
+    #[test]
+    fn test_external_document_binding() {
+        let document = vec![7u8; 1 << 20];
+        let hash = Transaction::hash_streaming(document.as_slice()).unwrap();
+
+        let plain = Transaction::with_nonce("recipient", 1000, None, 1).with_timestamp(1);
+        let bound = plain.clone().with_metadata_hash(hash);
+        assert_ne!(bound.id, plain.id);
+        assert_ne!(bound.signing_hash(), plain.signing_hash());
+        assert!(bound.matches_metadata_document(document.as_slice()).unwrap());
+        assert!(!plain.matches_metadata_document(document.as_slice()).unwrap());
+
+        // Changing one byte of the document breaks the binding
+        let mut altered = document.clone();
+        altered[12_345] ^= 1;
+        let altered_hash = Transaction::hash_streaming(altered.as_slice()).unwrap();
+        assert_ne!(altered_hash, hash);
+        assert!(!bound.matches_metadata_document(altered.as_slice()).unwrap());
+        assert_ne!(plain.with_metadata_hash(altered_hash).id, bound.id);
+
+        let decoded = Transaction::from_bytes(&bound.to_bytes()).unwrap();
+        assert_eq!(decoded.metadata_hash, Some(hash));
+    }
+
+    #[test]
+    fn test_signing_payload_with_context() {
+        let tx = Transaction::new("recipient", 1000, None);
+        let context = |chain_id| IntentContext { chain_id, wallet_id: "wallet".to_string() };
+
+        let chain1 = tx.signing_payload_with_context(&context(1));
+        assert_eq!(chain1, tx.signing_payload_with_context(&context(1)));
+        assert_ne!(chain1, tx.signing_payload_with_context(&context(2)));
+        assert_ne!(chain1, tx.signing_payload("wallet"));
+    }
+
+    #[test]
+    fn test_extension_ids_do_not_collide() {
+        let tx = Transaction::new("recipient", 1000, None);
+        let tags =
+            |tags: &[&str]| tx.clone().with_tags(tags.iter().map(|t| t.to_string()).collect());
+
+        assert_ne!(tags(&["a,b"]).id, tags(&["a", "b"]).id);
+        let locked = tx.clone().with_not_before(5);
+        assert_ne!(locked.id, tags(&["nb5"]).id);
+
+        // Moving the time lock into a tag no longer keeps the id valid
+        let mut rewritten = locked.clone();
+        rewritten.not_before = None;
+        rewritten.tags = vec!["nb5".to_string()];
+        assert!(!rewritten.verify_id());
+
+        // Transactions without extensions keep their original ids
+        let legacy = format!("{}:{}::{}:{}:", tx.recipient, tx.amount, tx.timestamp, tx.nonce);
+        assert_eq!(tx.id, hex::encode(hash_message(legacy.as_bytes())));
+    }
 }
@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+use secp256k1::{ecdsa::Signature, PublicKey};
+use crate::crypto::verify_signature;
+use crate::error::Result;
+use crate::transaction::Transaction;
+use crate::wallet::compute_wallet_id;
+
+/// Check that a set of signatures approves a transaction for a key set,
+/// without needing a wallet
+///
+/// Signatures are verified against the id a wallet created with
+/// `threshold` and `authorized` has. For a wallet whose signers or
+/// threshold changed after creation, use `verify_threshold_for_wallet` with
+/// its `wallet_id()`.
+pub fn verify_threshold(
+    tx: &Transaction,
+    sigs: &[(Signature, PublicKey)],
+    authorized: &[PublicKey],
+    threshold: usize,
+) -> Result<bool> {
+    let keys_hex: Vec<String> = authorized.iter().map(|pk| hex::encode(pk.serialize())).collect();
+    let wallet_id = compute_wallet_id(threshold, &keys_hex);
+    verify_threshold_for_wallet(tx, sigs, authorized, threshold, &wallet_id)
+}
+
+/// Like `verify_threshold`, with the wallet id the signatures are bound to
+/// given explicitly
+///
+/// Returns `false` if any signature is invalid, from a key outside
+/// `authorized`, or from a key that already signed, and otherwise whether
+/// the number of signatures reaches `threshold`.
+pub fn verify_threshold_for_wallet(
+    tx: &Transaction,
+    sigs: &[(Signature, PublicKey)],
+    authorized: &[PublicKey],
+    threshold: usize,
+    wallet_id: &str,
+) -> Result<bool> {
+    let message = tx.signing_payload(wallet_id);
+    let mut signers = HashSet::new();
+
+    for (signature, pubkey) in sigs {
+        if !authorized.contains(pubkey)
+            || !signers.insert(pubkey.serialize())
+            || !verify_signature(&message, signature, pubkey)?
+        {
+            return Ok(false);
+        }
+    }
+
+    Ok(signers.len() >= threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+    use crate::wallet::MultisigWallet;
+
+    #[test]
+    fn test_verify_threshold() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let authorized = vec![pk1, pk2, pk3];
+        let wallet = MultisigWallet::new(2, authorized.clone()).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        let sig1 = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        let sig2 = tx.sign(&sk2, wallet.wallet_id()).unwrap();
+
+        assert!(verify_threshold(&tx, &[(sig1, pk1), (sig2, pk2)], &authorized, 2).unwrap());
+        assert!(!verify_threshold(&tx, &[(sig1, pk1)], &authorized, 2).unwrap());
+        assert!(!verify_threshold(&tx, &[(sig1, pk1), (sig1, pk1)], &authorized, 2).unwrap());
+        assert!(!verify_threshold(&tx, &[(sig1, pk1), (sig2, pk2)], &[pk1, pk3], 2).unwrap());
+    }
+
+    #[test]
+    fn test_verify_threshold_for_wallet() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        let sig = tx.sign(&sk1, "custom-wallet").unwrap();
+
+        assert!(verify_threshold_for_wallet(&tx, &[(sig, pk1)], &[pk1], 1, "custom-wallet").unwrap());
+        assert!(!verify_threshold_for_wallet(&tx, &[(sig, pk1)], &[pk1], 1, "other").unwrap());
+    }
+}
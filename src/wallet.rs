@@ -1,251 +1,5371 @@
 use std::collections::{HashMap, HashSet};
-use serde::{Deserialize, Serialize};
-use secp256k1::{PublicKey, ecdsa::Signature};
-use crate::transaction::Transaction;
-use crate::crypto::verify_signature;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::Arc;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use secp256k1::{Keypair, PublicKey, SecretKey, ecdsa::{RecoverableSignature, Signature}, schnorr};
+use crate::transaction::{
+    HashId, IdStrategy, IntentContext, Recipient, RecipientFormat, Transaction,
+};
+use crate::crypto::{
+    context, eip191_message, hash_message, is_low_s, normalize_signature, recover_public_key_with,
+    secret_key_matches_pubkey, sign_message_schnorr_with, sign_message_with,
+    verify_signature_schnorr_with, verify_signature_with, HashAlgorithm, MessageMode,
+    PubkeyEncoding, SignatureScheme,
+};
+use crate::builder::MultisigWalletBuilder;
+use crate::clock::{Clock, SystemClock};
 use crate::error::{MultisigError, Result};
+use crate::events::WalletEvent;
+use crate::policy::Policy;
+use crate::warnings::WalletWarning;
 
 /// Represents a multisig wallet with M-of-N signature requirement
-
+///
+/// Use `MultisigWallet::builder` when configuring more than the signer set.
+///
+/// Serde is derived through `remote = "Self"` so the trait impls below can
+/// rebuild `authorized_keys` after deserializing.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct MultisigWallet {
+    /// Identity bound into every signing payload, fixed at construction
+    #[serde(default)]
+    wallet_id: String,
     threshold: usize,
     total_signers: usize,
     
     #[serde(skip)]
     authorized_keys: Vec<PublicKey>,
     authorized_keys_hex: Vec<String>,
+
+    /// Voting power per signer, keyed by pubkey hex
+    #[serde(default)]
+    weights: HashMap<String, u32>,
+
+    /// Pubkey hexes that must sign every transaction, from `Policy::Required`
+    #[serde(default)]
+    required_signers: Vec<String>,
+
+    #[serde(default)]
+    scheme: SignatureScheme,
+
+    /// Signers that use a different scheme than `scheme`, by pubkey hex
+    #[serde(default)]
+    signer_schemes: HashMap<String, SignatureScheme>,
+
+    /// Digest signers apply to the signing payload
+    #[serde(default)]
+    hash_algorithm: HashAlgorithm,
+
+    /// Framing applied to the signing payload, fixed for every signature
+    #[serde(default)]
+    message_mode: MessageMode,
+
+    /// Chain signatures are bound to, if any
+    #[serde(default)]
+    chain_id: Option<u64>,
+
+    /// Refuse high-S ECDSA signatures instead of normalizing them
+    #[serde(default)]
+    require_low_s: bool,
     pending_transactions: HashMap<String, PendingTransaction>,
+
+    /// Ids of every transaction this wallet has executed, kept so they can
+    /// never be proposed again
+    #[serde(default)]
+    executed_ids: HashSet<String>,
+
+    /// Proof of execution for every executed transaction, by id
+    #[serde(default)]
+    receipts: HashMap<String, ExecutionReceipt>,
+
+    /// Nonces already proposed, per recipient
+    #[serde(default)]
+    used_nonces: HashMap<String, HashSet<u64>>,
+
+    /// Delegate key allowed to sign in place of each principal, both as
+    /// compressed pubkey hex
+    #[serde(default)]
+    delegations: HashMap<String, String>,
+
+    /// Largest amount a single transaction may move
+    #[serde(default)]
+    max_amount: Option<u128>,
+
+    /// Lifetime in seconds given to proposals without their own expiry
+    #[serde(default)]
+    default_expiry: Option<u64>,
+
+    /// How far in the future, in seconds, a proposal's timestamp may be
+    #[serde(default = "default_max_clock_skew")]
+    max_clock_skew: u64,
+
+    /// How old, in seconds, a proposal's timestamp may be
+    #[serde(default)]
+    max_age: Option<u64>,
+
+    /// Most transactions, executed ones included until pruned, the wallet
+    /// will hold
+    #[serde(default)]
+    max_pending: Option<usize>,
+
+    #[serde(default = "default_allow_zero_amount")]
+    allow_zero_amount: bool,
+
+    #[serde(default)]
+    recipient_format: RecipientFormat,
+
+    /// What `add_signature` does with a repeat signature from one signer
+    #[serde(default)]
+    duplicate_policy: DuplicatePolicy,
+
+    /// Append-only audit log
+    #[serde(default)]
+    events: Vec<WalletEvent>,
+
+    #[serde(default)]
+    metrics: WalletMetrics,
+
+    #[serde(skip)]
+    on_ready: ReadyCallback,
+
+    #[serde(skip)]
+    clock: WalletClock,
+
+    #[serde(skip)]
+    id_strategy: WalletIdStrategy,
 }
 
-/// Represents a transaction awaiting signatures
+/// Closure run when a transaction first meets the wallet policy
+///
+/// Wrapped so the wallet can keep deriving `Clone` and `Debug`; clones share
+/// the same closure.
+#[derive(Clone, Default)]
+struct ReadyCallback(Option<Arc<ReadyFn>>);
+
+type ReadyFn = dyn Fn(&Transaction) + Send + Sync;
+
+impl std::fmt::Debug for ReadyCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = if self.0.is_some() { "set" } else { "unset" };
+        write!(f, "ReadyCallback({})", state)
+    }
+}
+
+/// Clock the wallet reads time from, `SystemClock` unless replaced
+///
+/// Wrapped like `ReadyCallback`; clones share the same clock.
+#[derive(Clone)]
+struct WalletClock(Arc<dyn Clock>);
+
+impl Default for WalletClock {
+    fn default() -> Self {
+        WalletClock(Arc::new(SystemClock))
+    }
+}
+
+impl std::fmt::Debug for WalletClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WalletClock({})", self.0.now_unix())
+    }
+}
+
+/// Id strategy proposals are checked against, `HashId` unless replaced
+///
+/// Wrapped like `ReadyCallback`; clones share the same strategy.
+#[derive(Clone)]
+struct WalletIdStrategy(Arc<dyn IdStrategy>);
+
+impl Default for WalletIdStrategy {
+    fn default() -> Self {
+        WalletIdStrategy(Arc::new(HashId))
+    }
+}
+
+impl std::fmt::Debug for WalletIdStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("WalletIdStrategy")
+    }
+}
 
+fn default_allow_zero_amount() -> bool {
+    true
+}
+
+fn default_max_clock_skew() -> u64 {
+    5 * 60
+}
+
+/// Domain tag every key-control challenge starts with
+const CHALLENGE_TAG: &str = "multisig-rs/challenge/v1:";
+
+/// Represents a transaction awaiting signatures
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct PendingTransaction {
     transaction: Transaction,
     
     signatures: HashMap<String, String>,
+
+    /// Pubkey hexes of signers who vetoed the transaction
+    #[serde(default)]
+    rejections: HashSet<String>,
     
     executed: bool,
+
+    #[serde(default)]
+    rejected: bool,
+
+    /// Deadline assigned from the wallet's default expiry when the
+    /// transaction did not carry its own
+    #[serde(default)]
+    expires_at: Option<u64>,
+
+    /// Whether the on-ready callback has already fired
+    #[serde(default)]
+    ready_notified: bool,
+
+    /// Hex `signing_hash` of the transaction as proposed, the body every
+    /// signature must cover
+    #[serde(default)]
+    body_hash: String,
+
+    /// Signers whose stored signature this process has verified against
+    /// `body_hash`; rebuilt from scratch after loading
+    #[serde(skip)]
+    verified: HashSet<String>,
+}
+
+/// Portable form of a pending transaction and the signatures collected so
+/// far, exchanged by `export_pending` and `import_pending`
+#[derive(Serialize, Deserialize)]
+struct PendingExport {
+    transaction: Transaction,
+    signatures: HashMap<String, String>,
+}
+
+impl PendingTransaction {
+    fn is_expired_at(&self, now: u64) -> bool {
+        self.transaction.is_expired_at(now) || self.expires_at.is_some_and(|t| now > t)
+    }
+
+    /// Fail with `TransactionIdMismatch` if the stored transaction no longer
+    /// matches what was proposed
+    fn check_body(&self) -> Result<()> {
+        if hex::encode(self.transaction.signing_hash()) != self.body_hash {
+            return Err(MultisigError::TransactionIdMismatch);
+        }
+        Ok(())
+    }
+
+    /// Whether `sig` is the stored signature of `pubkey_hex` and was already
+    /// verified against the unchanged body
+    ///
+    /// A body that changed since proposal invalidates every cached result.
+    fn is_verified(&self, pubkey_hex: &str, sig: &str) -> bool {
+        self.verified.contains(pubkey_hex)
+            && self.signatures.get(pubkey_hex).is_some_and(|stored| stored == sig)
+            && self.check_body().is_ok()
+    }
 }
 
 impl MultisigWallet {
 
     /// Create a new multisig wallet
-
     pub fn new(threshold: usize, authorized_keys: Vec<PublicKey>) -> Result<Self> {
-        let total_signers = authorized_keys.len();
+        let signers = authorized_keys.into_iter().map(|pk| (pk, 1)).collect();
+        Self::new_weighted(threshold, signers)
+    }
+
+    /// Create a multisig wallet governed by an approval policy
+    ///
+    /// Mandatory keys of a `Policy::Required` must be among `authorized_keys`.
+    pub fn new_with_policy(policy: Policy, authorized_keys: Vec<PublicKey>) -> Result<Self> {
+        if let Some(outsider) = policy.mandatory().iter().find(|pk| !authorized_keys.contains(pk)) {
+            return Err(MultisigError::UnauthorizedSigner {
+                pubkey_hex: hex::encode(outsider.serialize()),
+            });
+        }
+
+        let mut wallet = Self::new(policy.threshold(), authorized_keys)?;
+        wallet.required_signers = policy
+            .mandatory()
+            .iter()
+            .map(|pk| hex::encode(pk.serialize()))
+            .collect();
+        Ok(wallet)
+    }
+
+    /// The approval policy the wallet enforces
+    pub fn policy(&self) -> Policy {
+        if self.required_signers.is_empty() {
+            return Policy::Threshold(self.threshold);
+        }
+
+        Policy::Required {
+            mandatory: self.required_signers
+                .iter()
+                .filter_map(|pk_hex| parse_pubkey_hex(pk_hex).ok())
+                .collect(),
+            threshold: self.threshold,
+        }
+    }
+
+    /// Start configuring a wallet with `MultisigWalletBuilder`
+    pub fn builder() -> MultisigWalletBuilder {
+        MultisigWalletBuilder::new()
+    }
+
+    /// Create a multisig wallet from hex-encoded public keys
+    pub fn from_hex_keys(threshold: usize, keys_hex: Vec<String>) -> Result<Self> {
+        Self::new(threshold, parse_pubkey_list(&keys_hex)?)
+    }
+
+    /// Rebuild a wallet around a finalized transaction for offline audit
+    ///
+    /// The wallet is created from `threshold` and `keys_hex` and holds the
+    /// transaction as pending, with every signature that verifies. The
+    /// returned flag is `verify_signed_transaction` on the bundle, also
+    /// requiring the transaction id to match its body. Proposal checks such
+    /// as expiry and clock skew are skipped, since an audit usually happens
+    /// long after signing.
+    pub fn from_export(
+        threshold: usize,
+        keys_hex: Vec<String>,
+        signed: SignedTransaction,
+    ) -> Result<(Self, bool)> {
+        let mut wallet = Self::from_hex_keys(threshold, keys_hex)?;
+        let id_valid = wallet.id_strategy.0.is_valid_id(&signed.transaction);
+        let (algorithm, message) = wallet.signed_message(&signed.transaction);
+
+        let mut loaded = Vec::new();
+        for (pk_hex, sig) in &signed.signatures {
+            let (Ok(signer_pubkey), Ok(signature)) =
+                (parse_pubkey_hex(pk_hex), StoredSignature::decode(sig))
+            else {
+                continue;
+            };
+            let canonical_hex = hex::encode(signer_pubkey.serialize());
+            if wallet.is_authorized(&signer_pubkey)
+                && signature.scheme() == wallet.scheme_of(&canonical_hex)
+                && signature.verify(algorithm, &message, &signer_pubkey)?
+            {
+                loaded.push((canonical_hex, sig.clone()));
+            }
+        }
+
+        let tx_id = wallet.insert_proposal(signed.transaction.clone());
+        let pending = wallet.pending_transactions.get_mut(&tx_id).unwrap();
+        for (pk_hex, sig) in loaded {
+            pending.verified.insert(pk_hex.clone());
+            pending.signatures.entry(pk_hex).or_insert(sig);
+        }
+
+        // Loaded signatures are cached as verified, so this only re-checks
+        // the ones that were left out
+        let valid = id_valid && wallet.verify_signed_transaction(&signed)?;
+        Ok((wallet, valid))
+    }
+
+    /// Create a multisig wallet where each signer carries a voting weight
+    ///
+    /// The threshold is compared against the summed weight of the signers
+    /// who signed rather than the number of signatures.
+    pub fn new_weighted(threshold: usize, signers: Vec<(PublicKey, u32)>) -> Result<Self> {
+        if signers.iter().any(|(_, weight)| *weight == 0) {
+            return Err(MultisigError::InvalidWeight);
+        }
+
+        // A repeated key would count as several signers while only one
+        // person holds it
+        let mut seen = HashSet::with_capacity(signers.len());
+        for (index, (pk, _)) in signers.iter().enumerate() {
+            let encoded = pk.serialize();
+            // `PublicKey` is a curve point by construction, but keys built
+            // through the raw FFI layer skip that check
+            if PublicKey::from_slice(&encoded).is_err() {
+                return Err(MultisigError::InvalidPublicKey { index: Some(index) });
+            }
+            if !seen.insert(encoded) {
+                return Err(MultisigError::DuplicateSigner);
+            }
+        }
+
+        let total_signers = signers.len();
+        let total_weight: usize = signers.iter().map(|(_, weight)| *weight as usize).sum();
         
         // Validate threshold
-
-        if threshold == 0 || threshold > total_signers {
+        if threshold == 0 || threshold > total_weight {
             return Err(MultisigError::InvalidThreshold {
                 m: threshold,
-                n: total_signers,
+                n: total_weight,
             });
         }
         
         // Convert public keys to hex for serialization
+        let mut authorized_keys = Vec::with_capacity(total_signers);
+        let mut authorized_keys_hex = Vec::with_capacity(total_signers);
+        let mut weights = HashMap::with_capacity(total_signers);
 
-        let authorized_keys_hex: Vec<String> = authorized_keys
-            .iter()
-            .map(|pk| hex::encode(pk.serialize()))
-            .collect();
+        for (pk, weight) in signers {
+            let pk_hex = hex::encode(pk.serialize());
+            weights.insert(pk_hex.clone(), weight);
+            authorized_keys_hex.push(pk_hex);
+            authorized_keys.push(pk);
+        }
         
         Ok(MultisigWallet {
+            wallet_id: compute_wallet_id(threshold, &authorized_keys_hex),
             threshold,
             total_signers,
             authorized_keys,
             authorized_keys_hex,
+            weights,
+            required_signers: Vec::new(),
+            scheme: SignatureScheme::default(),
+            signer_schemes: HashMap::new(),
+            hash_algorithm: HashAlgorithm::default(),
+            message_mode: MessageMode::default(),
+            chain_id: None,
+            require_low_s: false,
             pending_transactions: HashMap::new(),
+            executed_ids: HashSet::new(),
+            receipts: HashMap::new(),
+            used_nonces: HashMap::new(),
+            delegations: HashMap::new(),
+            max_amount: None,
+            default_expiry: None,
+            max_clock_skew: default_max_clock_skew(),
+            max_age: None,
+            max_pending: None,
+            allow_zero_amount: default_allow_zero_amount(),
+            recipient_format: RecipientFormat::default(),
+            duplicate_policy: DuplicatePolicy::default(),
+            events: Vec::new(),
+            metrics: WalletMetrics::default(),
+            on_ready: ReadyCallback::default(),
+            clock: WalletClock::default(),
+            id_strategy: WalletIdStrategy::default(),
         })
     }
-    
-    /// Propose a new transaction
 
-    pub fn propose_transaction(&mut self, transaction: Transaction) -> Result<()> {
-        let tx_id = transaction.id.clone();
-        
-        let pending = PendingTransaction {
-            transaction,
-            signatures: HashMap::new(),
-            executed: false,
+    /// Cap the amount any single proposed transaction may move
+    pub fn with_max_amount(mut self, limit: u128) -> Self {
+        self.max_amount = Some(limit);
+        self
+    }
+
+    /// Expire proposals that carry no expiry of their own `ttl` seconds after
+    /// they are proposed
+    pub fn with_default_expiry(mut self, ttl: u64) -> Self {
+        self.default_expiry = Some(ttl);
+        self
+    }
+
+    /// Choose whether zero-amount transactions may be proposed (allowed by
+    /// default)
+    pub fn with_allow_zero_amount(mut self, allow: bool) -> Self {
+        self.allow_zero_amount = allow;
+        self
+    }
+
+    /// Require proposed recipients to match a format (any non-empty string by
+    /// default)
+    pub fn with_recipient_format(mut self, format: RecipientFormat) -> Self {
+        self.recipient_format = format;
+        self
+    }
+
+    /// Choose how `add_signature` treats a second signature from a signer
+    /// who already signed (`DuplicatePolicy::Reject` by default)
+    pub fn with_duplicate_policy(mut self, policy: DuplicatePolicy) -> Self {
+        self.duplicate_policy = policy;
+        self
+    }
+
+    /// Set how many seconds ahead of the local clock a proposal's timestamp
+    /// may be (five minutes by default)
+    pub fn with_max_clock_skew(mut self, skew: u64) -> Self {
+        self.max_clock_skew = skew;
+        self
+    }
+
+    /// Reject proposals whose timestamp is more than `max_age` seconds old
+    pub fn with_max_age(mut self, max_age: u64) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Refuse new proposals once the wallet holds `limit` unexecuted
+    /// transactions
+    ///
+    /// Executed transactions free their slot straight away; `prune_executed`
+    /// only reclaims their storage.
+    pub fn with_max_pending(mut self, limit: usize) -> Self {
+        self.max_pending = Some(limit);
+        self
+    }
+
+    /// Select the signature algorithm signers must use (ECDSA by default)
+    ///
+    /// Signers given their own scheme with `set_signer_scheme` keep it.
+    pub fn with_signature_scheme(mut self, scheme: SignatureScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Select the digest signers apply before signing (SHA-256 by default)
+    pub fn with_hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = algorithm;
+        self
+    }
+
+    /// Select how the signing payload is framed before hashing
+    ///
+    /// `MessageMode::Eip191` accepts `personal_sign` signatures from
+    /// Ethereum tooling and always hashes with Keccak-256, overriding the
+    /// hash algorithm. All signers must use the same mode, so set it before
+    /// any signature is collected.
+    pub fn with_message_mode(mut self, mode: MessageMode) -> Self {
+        self.message_mode = mode;
+        self
+    }
+
+    /// Bind every signature to `chain_id`, so signatures collected for the
+    /// same transaction on another chain are refused
+    ///
+    /// Signers sign `Transaction::signing_payload_with_context` with
+    /// `intent_context()`. Like the message mode, set it before any signature
+    /// is collected.
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// The context signatures are bound to, if the wallet has a chain id
+    pub fn intent_context(&self) -> Option<IntentContext> {
+        self.chain_id.map(|chain_id| IntentContext {
+            chain_id,
+            wallet_id: self.wallet_id.clone(),
+        })
+    }
+
+    /// Read time from `clock` instead of the system clock
+    ///
+    /// Every time-based check (timestamps, expiry, time locks) and every
+    /// recorded time goes through it. The clock is not serialized; a loaded
+    /// wallet uses the system clock until this is called again.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = WalletClock(Arc::new(clock));
+        self
+    }
+
+    /// Accept proposals whose ids come from `strategy` instead of `HashId`
+    ///
+    /// Set this once, before the first proposal; switching strategies on a
+    /// wallet that already holds transactions is unsupported. Ids must still
+    /// be unique. With a strategy that does not hash the contents, the body
+    /// hash recorded at proposal is what ties signatures to the contents.
+    /// The strategy is not serialized and must be set again after loading.
+    pub fn with_id_strategy(mut self, strategy: impl IdStrategy + 'static) -> Self {
+        self.id_strategy = WalletIdStrategy(Arc::new(strategy));
+        self
+    }
+
+    /// Current Unix time according to the wallet's clock
+    pub fn now(&self) -> u64 {
+        self.clock.0.now_unix()
+    }
+
+    /// Reject high-S ECDSA signatures with `NonCanonicalSignature`
+    ///
+    /// By default high-S signatures are accepted and stored in low-S form.
+    pub fn with_require_low_s(mut self, require: bool) -> Self {
+        self.require_low_s = require;
+        self
+    }
+
+    /// The digest and message signers sign for a transaction in this wallet
+    fn signed_message(&self, transaction: &Transaction) -> (HashAlgorithm, Vec<u8>) {
+        let payload = match self.intent_context() {
+            Some(context) => transaction.signing_payload_with_context(&context),
+            None => transaction.signing_payload(&self.wallet_id),
         };
-        
-        self.pending_transactions.insert(tx_id, pending);
-        Ok(())
+        self.frame_message(payload)
     }
-    
-    /// Add a signature to a pending transaction
 
-    pub fn add_signature(
-        &mut self,
-        tx_id: &str,
-        signature: Signature,
-        signer_pubkey: &PublicKey,
-    ) -> Result<()> {
+    /// The digest and framing signers apply to any payload in this wallet
+    fn frame_message(&self, payload: Vec<u8>) -> (HashAlgorithm, Vec<u8>) {
+        match self.message_mode {
+            MessageMode::Standard => (self.hash_algorithm, payload),
+            MessageMode::Eip191 => (HashAlgorithm::Keccak256, eip191_message(&payload)),
+        }
+    }
 
-        // Check if signer is authorized
-        if !self.is_authorized(signer_pubkey) {
-            return Err(MultisigError::UnauthorizedSigner);
+    /// Bring an ECDSA signature to low-S form, or refuse it if the wallet
+    /// requires canonical signatures
+    fn canonicalize(&self, signature: &mut Signature) -> Result<()> {
+        if !is_low_s(signature) {
+            if self.require_low_s {
+                return Err(MultisigError::NonCanonicalSignature);
+            }
+            normalize_signature(signature);
         }
-        
-        // Get the pending transaction
-        let pending = self.pending_transactions
-            .get_mut(tx_id)
-            .ok_or(MultisigError::TransactionNotFound)?;
-        
-        // Check if already executed
-        if pending.executed {
-            return Err(MultisigError::TransactionAlreadyExecuted);
+        Ok(())
+    }
+
+    /// Authorize an additional signer
+    pub fn add_signer(&mut self, pubkey: PublicKey) -> Result<()> {
+        let pubkey_hex = hex::encode(pubkey.serialize());
+        if self.is_authorized(&pubkey) || self.is_delegate(&pubkey_hex) {
+            return Err(MultisigError::DuplicateSigner);
         }
-        
-        // Verify the signature
-        let tx_bytes = pending.transaction.to_bytes();
-        let is_valid = verify_signature(&tx_bytes, &signature, signer_pubkey)?;
-        
-        if !is_valid {
-            return Err(MultisigError::InvalidSignature);
+
+        self.weights.insert(pubkey_hex.clone(), 1);
+        self.authorized_keys_hex.push(pubkey_hex);
+        self.authorized_keys.push(pubkey);
+        self.total_signers = self.authorized_keys.len();
+
+        Ok(())
+    }
+
+    /// Revoke a signer's authorization
+    ///
+    /// Signatures the removed signer left on unexecuted transactions are
+    /// dropped. Executed transactions keep their signatures as a record of
+    /// what authorized them. A mandatory signer stops being mandatory, since
+    /// their signature could otherwise never be collected again.
+    pub fn remove_signer(&mut self, pubkey: &PublicKey) -> Result<()> {
+        let index = self.authorized_keys
+            .iter()
+            .position(|pk| pk == pubkey)
+            .ok_or_else(|| MultisigError::UnauthorizedSigner {
+                pubkey_hex: hex::encode(pubkey.serialize()),
+            })?;
+
+        // Removal must not leave the threshold unreachable
+        let remaining_weight = self.total_weight() - self.weight_of(&self.authorized_keys_hex[index]);
+        if self.threshold > remaining_weight {
+            return Err(MultisigError::InvalidThreshold {
+                m: self.threshold,
+                n: remaining_weight,
+            });
         }
-        
-        // Store the signature
-        let pubkey_hex = hex::encode(signer_pubkey.serialize());
-        let sig_hex = hex::encode(signature.serialize_compact());
-        
-        // Check for duplicate signature
-        if pending.signatures.contains_key(&pubkey_hex) {
-            return Err(MultisigError::DuplicateSignature);
+
+        self.authorized_keys.remove(index);
+        let pubkey_hex = self.authorized_keys_hex.remove(index);
+        self.weights.remove(&pubkey_hex);
+        self.signer_schemes.remove(&pubkey_hex);
+        self.required_signers.retain(|pk_hex| *pk_hex != pubkey_hex);
+        self.total_signers = self.authorized_keys.len();
+        self.drop_delegation(&pubkey_hex);
+
+        for pending in self.pending_transactions.values_mut() {
+            if !pending.executed {
+                pending.signatures.remove(&pubkey_hex);
+                pending.verified.remove(&pubkey_hex);
+                pending.rejections.remove(&pubkey_hex);
+            }
         }
-        
-        pending.signatures.insert(pubkey_hex, sig_hex);
-        
+
         Ok(())
     }
-    
-    /// Check if a transaction has enough signatures
 
-    pub fn has_enough_signatures(&self, tx_id: &str) -> Result<bool> {
-        let pending = self.pending_transactions
-            .get(tx_id)
-            .ok_or(MultisigError::TransactionNotFound)?;
-        
-        Ok(pending.signatures.len() >= self.threshold)
-    }
-    
-    /// Verify and execute a transaction if it has enough signatures
+    /// Replace one signer's key, keeping its weight and position
+    ///
+    /// Meant for a compromised key: the old key's signatures and
+    /// rejections on unexecuted transactions are dropped, so only the new
+    /// key can approve them. A mandatory signer stays mandatory under its new
+    /// key.
+    pub fn rotate_signer(&mut self, old: &PublicKey, new: PublicKey) -> Result<()> {
+        let index = self.authorized_keys
+            .iter()
+            .position(|pk| pk == old)
+            .ok_or_else(|| MultisigError::UnauthorizedSigner {
+                pubkey_hex: hex::encode(old.serialize()),
+            })?;
+        let new_hex = hex::encode(new.serialize());
+        if self.is_authorized(&new) || self.is_delegate(&new_hex) {
+            return Err(MultisigError::DuplicateSigner);
+        }
 
-    pub fn execute_transaction(&mut self, tx_id: &str) -> Result<Transaction> {
-        if !self.has_enough_signatures(tx_id)? {
-            let pending = self.pending_transactions.get(tx_id).unwrap();
-            return Err(MultisigError::InsufficientSignatures {
-                required: self.threshold,
-                actual: pending.signatures.len(),
-            });
+        let old_hex = std::mem::replace(&mut self.authorized_keys_hex[index], new_hex.clone());
+        self.authorized_keys[index] = new;
+        if let Some(weight) = self.weights.remove(&old_hex) {
+            self.weights.insert(new_hex.clone(), weight);
         }
-        
-        let pending = self.pending_transactions
-            .get_mut(tx_id)
-            .ok_or(MultisigError::TransactionNotFound)?;
-        
-        if pending.executed {
-            return Err(MultisigError::TransactionAlreadyExecuted);
+        for required in self.required_signers.iter_mut().filter(|pk_hex| **pk_hex == old_hex) {
+            *required = new_hex.clone();
         }
-        
-        pending.executed = true;
-        
-        
-        Ok(pending.transaction.clone())
+        if let Some(delegate_hex) = self.delegations.remove(&old_hex) {
+            self.delegations.insert(new_hex.clone(), delegate_hex);
+        }
+        if let Some(scheme) = self.signer_schemes.remove(&old_hex) {
+            self.signer_schemes.insert(new_hex.clone(), scheme);
+        }
+
+        for pending in self.pending_transactions.values_mut() {
+            if !pending.executed {
+                pending.signatures.remove(&old_hex);
+                pending.verified.remove(&old_hex);
+                pending.rejections.remove(&old_hex);
+            }
+        }
+
+        Ok(())
     }
-    
-    /// Check if a public key is authorized
 
-    fn is_authorized(&self, pubkey: &PublicKey) -> bool {
-        self.authorized_keys.iter().any(|pk| pk == pubkey)
+    /// Make one signer use a different signature scheme than the wallet
+    ///
+    /// Lets signers on ECDSA-only hardware share a wallet with Schnorr
+    /// signers. Each signature is checked against the scheme of the key
+    /// that made it, and signatures of either scheme count the same toward
+    /// the threshold.
+    pub fn set_signer_scheme(&mut self, pubkey: &PublicKey, scheme: SignatureScheme) -> Result<()> {
+        let pubkey_hex = hex::encode(pubkey.serialize());
+        if !self.is_authorized(pubkey) {
+            return Err(MultisigError::UnauthorizedSigner { pubkey_hex });
+        }
+
+        self.signer_schemes.insert(pubkey_hex, scheme);
+        Ok(())
     }
-    
-    /// Get the number of signatures for a transaction
 
-    pub fn get_signature_count(&self, tx_id: &str) -> Result<usize> {
-        let pending = self.pending_transactions
-            .get(tx_id)
-            .ok_or(MultisigError::TransactionNotFound)?;
-        
-        Ok(pending.signatures.len())
+    /// Signature scheme a key must sign with
+    pub fn signer_scheme(&self, pubkey: &PublicKey) -> SignatureScheme {
+        self.scheme_of(&hex::encode(pubkey.serialize()))
     }
-    
-    /// Get wallet information
 
-    pub fn info(&self) -> WalletInfo {
-        WalletInfo {
-            threshold: self.threshold,
-            total_signers: self.total_signers,
-            pending_count: self.pending_transactions.len(),
+    /// Let `delegate` sign in place of an authorized `principal`
+    ///
+    /// A delegate's signature fills the principal's slot, with the
+    /// principal's weight, and the two can never count twice for the same
+    /// transaction. Each principal has at most one delegate; adding a new
+    /// one replaces the old as with `remove_delegation`.
+    pub fn add_delegation(&mut self, principal: &PublicKey, delegate: PublicKey) -> Result<()> {
+        let principal_hex = hex::encode(principal.serialize());
+        if !self.is_authorized(principal) {
+            return Err(MultisigError::UnauthorizedSigner { pubkey_hex: principal_hex });
         }
-    }
-}
 
+        let delegate_hex = hex::encode(delegate.serialize());
+        if self.is_authorized(&delegate) || self.is_delegate(&delegate_hex) {
+            return Err(MultisigError::DuplicateSigner);
+        }
 
-#[derive(Debug, Serialize, Deserialize)]
+        self.drop_delegation(&principal_hex);
+        self.delegations.insert(principal_hex, delegate_hex);
+        Ok(())
+    }
+
+    /// Revoke a principal's delegate
+    ///
+    /// Signatures the delegate left on unexecuted transactions are dropped,
+    /// so they stop counting toward the principal's slot.
+    pub fn remove_delegation(&mut self, principal: &PublicKey) -> Result<()> {
+        let principal_hex = hex::encode(principal.serialize());
+        if !self.delegations.contains_key(&principal_hex) {
+            return Err(MultisigError::UnauthorizedSigner { pubkey_hex: principal_hex });
+        }
+
+        self.drop_delegation(&principal_hex);
+        Ok(())
+    }
+
+    /// Forget a principal's delegate and its signatures on unexecuted
+    /// transactions
+    fn drop_delegation(&mut self, principal_hex: &str) {
+        let Some(delegate_hex) = self.delegations.remove(principal_hex) else {
+            return;
+        };
+        for pending in self.pending_transactions.values_mut() {
+            if !pending.executed {
+                pending.signatures.remove(&delegate_hex);
+                pending.verified.remove(&delegate_hex);
+            }
+        }
+    }
+
+    /// Change the number of signatures required to execute
+    ///
+    /// The new threshold applies immediately to every pending transaction:
+    /// lowering it can make a transaction executable with the signatures it
+    /// already has, raising it can leave a previously ready transaction short.
+    /// Transactions that were already executed are left as they are.
+    pub fn set_threshold(&mut self, new_threshold: usize) -> Result<()> {
+        let total_weight = self.total_weight();
+        if new_threshold == 0 || new_threshold > total_weight {
+            return Err(MultisigError::InvalidThreshold {
+                m: new_threshold,
+                n: total_weight,
+            });
+        }
+
+        self.threshold = new_threshold;
+        Ok(())
+    }
+
+    /// Propose a new transaction
+    pub fn propose_transaction(&mut self, transaction: Transaction) -> Result<()> {
+        self.validate_proposal(&transaction)?;
+        self.insert_proposal(transaction);
+        Ok(())
+    }
+
+    /// Propose several transactions, all or none
+    ///
+    /// Every transaction is validated, against the wallet and against the
+    /// rest of the batch, before any is added. On error the wallet is left
+    /// exactly as it was. Returns the new ids in input order.
+    pub fn propose_transactions(&mut self, transactions: Vec<Transaction>) -> Result<Vec<String>> {
+        self.check_capacity(transactions.len())?;
+        let mut ids = HashSet::new();
+        let mut nonces = HashSet::new();
+        for transaction in &transactions {
+            self.validate_proposal(transaction)?;
+            if !ids.insert(&transaction.id) {
+                return Err(MultisigError::DuplicateTransaction);
+            }
+            if !nonces.insert((transaction.recipient.to_string(), transaction.nonce)) {
+                return Err(MultisigError::DuplicateNonce {
+                    recipient: transaction.recipient.to_string(),
+                    nonce: transaction.nonce,
+                });
+            }
+        }
+
+        Ok(transactions
+            .into_iter()
+            .map(|transaction| self.insert_proposal(transaction))
+            .collect())
+    }
+
+    /// Add an already validated proposal, returning its id
+    fn insert_proposal(&mut self, transaction: Transaction) -> String {
+        let tx_id = transaction.id.clone();
+        let now = self.now();
+
+        let expires_at = match transaction.expires_at {
+            Some(_) => None,
+            None => self.default_expiry.map(|ttl| now.saturating_add(ttl)),
+        };
+        
+        let body_hash = hex::encode(transaction.signing_hash());
+        let pending = PendingTransaction {
+            transaction,
+            signatures: HashMap::new(),
+            rejections: HashSet::new(),
+            executed: false,
+            rejected: false,
+            expires_at,
+            ready_notified: false,
+            body_hash,
+            verified: HashSet::new(),
+        };
+        
+        self.used_nonces
+            .entry(pending.transaction.recipient.to_string())
+            .or_default()
+            .insert(pending.transaction.nonce);
+        self.pending_transactions.insert(tx_id.clone(), pending);
+        self.metrics.proposals += 1;
+        self.events.push(WalletEvent::TransactionProposed {
+            tx_id: tx_id.clone(),
+            timestamp: now,
+        });
+        tx_id
+    }
+    
+
+    /// Next unused nonce for transactions to `recipient`
+    ///
+    /// One past the highest nonce proposed to that recipient so far, so
+    /// transactions built with it are ordered and never collide.
+    pub fn next_nonce(&self, recipient: &str) -> u64 {
+        self.used_nonces
+            .get(recipient)
+            .and_then(|nonces| nonces.iter().max())
+            .map_or(0, |max| max.saturating_add(1))
+    }
+
+    /// Build a transaction carrying the recipient's next nonce and propose it
+    ///
+    /// Returns the id of the new transaction.
+    pub fn propose_with_next_nonce(
+        &mut self,
+        recipient: impl Into<Recipient>,
+        amount: u128,
+        metadata: Option<String>,
+    ) -> Result<String> {
+        let recipient = recipient.into();
+        let nonce = self.next_nonce(&recipient.to_string());
+        let transaction = Transaction::with_nonce(recipient, amount, metadata, nonce);
+        let tx_id = transaction.id.clone();
+        self.propose_transaction(transaction)?;
+        Ok(tx_id)
+    }
+
+    /// Propose a transaction and add the proposer's own signature in one step
+    ///
+    /// The proposer must be an authorized signer whose public key matches
+    /// `proposer_sk`; both are checked before anything is proposed. The
+    /// signature follows the wallet's scheme, hash algorithm and message
+    /// mode. Returns the id of the new transaction. On error the wallet is
+    /// left unchanged.
+    pub fn propose_and_sign(
+        &mut self,
+        transaction: Transaction,
+        proposer_sk: &SecretKey,
+        proposer_pk: &PublicKey,
+    ) -> Result<String> {
+        if !self.is_authorized(proposer_pk) {
+            return Err(MultisigError::UnauthorizedSigner {
+                pubkey_hex: hex::encode(proposer_pk.serialize()),
+            });
+        }
+        if !secret_key_matches_pubkey(proposer_sk, proposer_pk) {
+            return Err(MultisigError::InvalidPrivateKey);
+        }
+        self.validate_proposal(&transaction)?;
+        // Checked up front so a signature that cannot be added never leaves
+        // an unsigned proposal behind
+        if transaction.is_expired_at(self.now()) {
+            return Err(MultisigError::TransactionExpired);
+        }
+
+        let (algorithm, message) = self.signed_message(&transaction);
+        let signature = match self.signer_scheme(proposer_pk) {
+            SignatureScheme::Ecdsa => {
+                StoredSignature::Ecdsa(sign_message_with(algorithm, &message, proposer_sk)?)
+            }
+            SignatureScheme::Schnorr => {
+                let keypair = Keypair::from_secret_key(context(), proposer_sk);
+                StoredSignature::Schnorr(sign_message_schnorr_with(algorithm, &message, &keypair)?)
+            }
+        };
+
+        let tx_id = self.insert_proposal(transaction);
+        self.insert_signature(&tx_id, signature, proposer_pk, DuplicatePolicy::Reject)?;
+        Ok(tx_id)
+    }
+
+    /// Check a transaction against the wallet's proposal rules
+    fn validate_proposal(&self, transaction: &Transaction) -> Result<()> {
+        self.check_capacity(1)?;
+
+        if !self.id_strategy.0.is_valid_id(transaction) {
+            return Err(MultisigError::TransactionIdMismatch);
+        }
+
+        // Replay protection
+        if self.executed_ids.contains(&transaction.id) {
+            return Err(MultisigError::TransactionAlreadyExecuted);
+        }
+
+        if self.pending_transactions.contains_key(&transaction.id) {
+            return Err(MultisigError::DuplicateTransaction);
+        }
+
+        let nonce_used = self.used_nonces
+            .get(&transaction.recipient.to_string())
+            .is_some_and(|nonces| nonces.contains(&transaction.nonce));
+        if nonce_used {
+            return Err(MultisigError::DuplicateNonce {
+                recipient: transaction.recipient.to_string(),
+                nonce: transaction.nonce,
+            });
+        }
+
+        transaction.validate(self.recipient_format)?;
+
+        let now = self.now();
+        let too_new = transaction.timestamp > now.saturating_add(self.max_clock_skew);
+        let too_old = self
+            .max_age
+            .is_some_and(|max_age| transaction.timestamp < now.saturating_sub(max_age));
+        if too_new || too_old {
+            return Err(MultisigError::TimestampOutOfRange {
+                timestamp: transaction.timestamp,
+            });
+        }
+
+        transaction.total_cost()?;
+
+        if transaction.amount == 0 && !self.allow_zero_amount {
+            return Err(MultisigError::ZeroAmount);
+        }
+
+        if let Some(limit) = self.max_amount {
+            if transaction.amount > limit {
+                return Err(MultisigError::AmountExceedsLimit {
+                    amount: transaction.amount,
+                    limit,
+                });
+            }
+        }
+
+        Ok(())
+    }
+    
+    /// Add a signature to a pending transaction
+    ///
+    /// A second signature from a signer who already signed is handled by
+    /// the wallet's `DuplicatePolicy`. A replacement is logged as
+    /// `SignatureReplaced` and not counted in `signatures_added`.
+    pub fn add_signature(
+        &mut self,
+        tx_id: &str,
+        signature: Signature,
+        signer_pubkey: &PublicKey,
+    ) -> Result<()> {
+        let policy = self.duplicate_policy;
+        self.insert_signature(tx_id, StoredSignature::Ecdsa(signature), signer_pubkey, policy)
+    }
+
+    /// Add a 64-byte compact ECDSA signature produced outside the crate
+    ///
+    /// For hardware wallets and HSMs that sign `signing_payload` themselves
+    /// and never expose a `SecretKey`. Bytes that do not parse as a compact
+    /// signature fail with `InvalidSignature`; otherwise this behaves exactly
+    /// like `add_signature`.
+    pub fn add_signature_from_bytes(
+        &mut self,
+        tx_id: &str,
+        sig_bytes: &[u8],
+        signer_pubkey: &PublicKey,
+    ) -> Result<()> {
+        let signature = Signature::from_compact(sig_bytes).map_err(|_| {
+            MultisigError::InvalidSignature {
+                tx_id: tx_id.to_string(),
+                pubkey_hex: hex::encode(signer_pubkey.serialize()),
+            }
+        })?;
+        self.add_signature(tx_id, signature, signer_pubkey)
+    }
+
+    /// Add a recoverable signature, deriving the signer from the signature
+    ///
+    /// The recovered key may be an authorized signer or a delegate. Returns
+    /// the recovered public key of the signer.
+    pub fn add_recoverable_signature(
+        &mut self,
+        tx_id: &str,
+        signature: RecoverableSignature,
+    ) -> Result<PublicKey> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        let (algorithm, message) = self.signed_message(&pending.transaction);
+        let signer_pubkey = recover_public_key_with(algorithm, &message, &signature)?;
+        if self.principal_of(&hex::encode(signer_pubkey.serialize())).is_none() {
+            return Err(MultisigError::UnauthorizedSigner {
+                pubkey_hex: hex::encode(signer_pubkey.serialize()),
+            });
+        }
+
+        self.add_signature(tx_id, signature.to_standard(), &signer_pubkey)?;
+        Ok(signer_pubkey)
+    }
+
+    /// Add a Schnorr signature from a signer who uses Schnorr
+    pub fn add_schnorr_signature(
+        &mut self,
+        tx_id: &str,
+        signature: schnorr::Signature,
+        signer_pubkey: &PublicKey,
+    ) -> Result<()> {
+        let policy = self.duplicate_policy;
+        self.insert_signature(tx_id, StoredSignature::Schnorr(signature), signer_pubkey, policy)
+    }
+
+    /// Replace a signer's existing signature with a freshly verified one
+    ///
+    /// Unlike `add_signature`, a signer who already signed is not an error;
+    /// their old signature is overwritten and still counts once toward the
+    /// threshold. A signer who has not signed yet is simply added.
+    pub fn update_signature(
+        &mut self,
+        tx_id: &str,
+        signature: Signature,
+        signer_pubkey: &PublicKey,
+    ) -> Result<()> {
+        let policy = DuplicatePolicy::Replace;
+        self.insert_signature(tx_id, StoredSignature::Ecdsa(signature), signer_pubkey, policy)
+    }
+
+    /// Verify a signature of either scheme and record it, handling an
+    /// earlier signature from the same signer according to `duplicates`
+    fn insert_signature(
+        &mut self,
+        tx_id: &str,
+        mut signature: StoredSignature,
+        signer_pubkey: &PublicKey,
+        duplicates: DuplicatePolicy,
+    ) -> Result<()> {
+        if signature.scheme() != self.signer_scheme(signer_pubkey) {
+            return Err(MultisigError::SignatureSchemeMismatch);
+        }
+
+        if let StoredSignature::Ecdsa(sig) = &mut signature {
+            self.canonicalize(sig)?;
+        }
+
+        let pubkey_hex = hex::encode(signer_pubkey.serialize());
+
+        // Check if signer is authorized, directly or as a delegate
+        let Some(principal_hex) = self.principal_of(&pubkey_hex) else {
+            return Err(MultisigError::UnauthorizedSigner { pubkey_hex });
+        };
+        
+        // Get the pending transaction
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+        
+        // Check if already executed
+        if pending.executed {
+            return Err(MultisigError::TransactionAlreadyExecuted);
+        }
+
+        if pending.rejected {
+            return Err(MultisigError::TransactionRejected);
+        }
+
+        if pending.is_expired_at(self.now()) {
+            return Err(MultisigError::TransactionExpired);
+        }
+        
+        // Verify the signature against the body that was proposed
+        pending.check_body()?;
+        let (algorithm, message) = self.signed_message(&pending.transaction);
+        let is_valid = signature.verify(algorithm, &message, signer_pubkey)?;
+        
+        if !is_valid {
+            return Err(MultisigError::InvalidSignature {
+                tx_id: tx_id.to_string(),
+                pubkey_hex,
+            });
+        }
+        
+        // Store the signature
+        let sig_hex = signature.encode();
+        
+        // Duplicates are keyed on the signer, never on signature bytes, and
+        // ECDSA signatures were already brought to low-S above, so a
+        // re-encoded copy of a signature cannot count as a second entry. A
+        // principal and its delegate share one slot.
+        let repeat = pending.signatures.contains_key(&pubkey_hex);
+        let slot_taken = pending.signatures.keys().any(|pk_hex| {
+            *pk_hex != pubkey_hex && self.principal_of(pk_hex).as_ref() == Some(&principal_hex)
+        });
+        if slot_taken || (repeat && duplicates == DuplicatePolicy::Reject) {
+            return Err(MultisigError::DuplicateSignature);
+        }
+        if repeat && duplicates == DuplicatePolicy::Ignore {
+            return Ok(());
+        }
+        
+        // Signing withdraws any earlier veto from the same signer
+        let pending = self.pending_transactions.get_mut(tx_id).unwrap();
+        pending.rejections.remove(&principal_hex);
+        pending.signatures.insert(pubkey_hex.clone(), sig_hex);
+        pending.verified.insert(pubkey_hex.clone());
+
+        // An overwrite is logged as such and does not count as a new signature
+        let (tx_id_owned, timestamp) = (tx_id.to_string(), self.now());
+        let event = if repeat {
+            WalletEvent::SignatureReplaced { tx_id: tx_id_owned, signer: pubkey_hex, timestamp }
+        } else {
+            self.metrics.signatures_added += 1;
+            WalletEvent::SignatureAdded { tx_id: tx_id_owned, signer: pubkey_hex, timestamp }
+        };
+        self.events.push(event);
+        self.notify_if_ready(tx_id);
+        
+        Ok(())
+    }
+    
+    /// Register a closure to run when a transaction first gathers enough
+    /// signatures to satisfy the wallet policy
+    ///
+    /// The closure fires at most once per transaction, synchronously inside
+    /// the call that added the deciding signature, and replaces any earlier
+    /// closure. It must be `Send + Sync` so the wallet can still move between
+    /// threads; it runs while the wallet is mutably borrowed, so it cannot
+    /// call back into the same wallet and should hand slow work (network
+    /// calls, broadcasts) off to another thread or queue.
+    pub fn set_on_ready(&mut self, cb: Box<dyn Fn(&Transaction) + Send + Sync>) {
+        self.on_ready = ReadyCallback(Some(Arc::from(cb)));
+    }
+
+    /// Fire the on-ready callback if the transaction just met the policy
+    fn notify_if_ready(&mut self, tx_id: &str) {
+        let Some(pending) = self.pending_transactions.get(tx_id) else {
+            return;
+        };
+        if pending.ready_notified || !self.meets_policy(pending) {
+            return;
+        }
+
+        let pending = self.pending_transactions.get_mut(tx_id).unwrap();
+        pending.ready_notified = true;
+        if let Some(cb) = &self.on_ready.0 {
+            cb(&pending.transaction);
+        }
+    }
+
+    /// Add several signatures to a pending transaction at once
+    ///
+    /// All signatures are verified with the shared secp256k1 context. Valid
+    /// signatures are stored even if others in the batch fail; the failures
+    /// are reported together in `BatchFailed`, indexed by their position in
+    /// `sigs`. Repeats from a signer who already signed follow the wallet's
+    /// `DuplicatePolicy`, as in `add_signature`. Returns the number of new
+    /// signatures added, not counting replacements.
+    ///
+    /// Problems with the transaction itself, such as it having been executed
+    /// since the signatures were collected, fail the whole batch up front
+    /// with the matching error and leave the wallet untouched.
+    pub fn add_signatures_batch(
+        &mut self,
+        tx_id: &str,
+        sigs: Vec<(Signature, PublicKey)>,
+    ) -> Result<usize> {
+        let any_ecdsa = self.authorized_keys_hex
+            .iter()
+            .any(|pk_hex| self.scheme_of(pk_hex) == SignatureScheme::Ecdsa);
+        if !any_ecdsa {
+            return Err(MultisigError::SignatureSchemeMismatch);
+        }
+
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        if pending.executed {
+            return Err(MultisigError::TransactionAlreadyExecuted);
+        }
+
+        if pending.rejected {
+            return Err(MultisigError::TransactionRejected);
+        }
+
+        if pending.is_expired_at(self.now()) {
+            return Err(MultisigError::TransactionExpired);
+        }
+
+        pending.check_body()?;
+        let (algorithm, message) = self.signed_message(&pending.transaction);
+        let mut accepted: Vec<(String, String, String)> = Vec::new();
+        let mut failures = Vec::new();
+
+        for (index, (mut signature, signer_pubkey)) in sigs.into_iter().enumerate() {
+            let pubkey_hex = hex::encode(signer_pubkey.serialize());
+            let Some(principal_hex) = self.principal_of(&pubkey_hex) else {
+                failures.push((index, MultisigError::UnauthorizedSigner { pubkey_hex }));
+                continue;
+            };
+
+            if self.scheme_of(&pubkey_hex) != SignatureScheme::Ecdsa {
+                failures.push((index, MultisigError::SignatureSchemeMismatch));
+                continue;
+            }
+
+            if let Err(e) = self.canonicalize(&mut signature) {
+                failures.push((index, e));
+                continue;
+            }
+
+            // Repeats from the same signer follow the duplicate policy; a
+            // slot filled by the principal or delegate is always a duplicate
+            let repeat = pending.signatures.contains_key(&pubkey_hex)
+                || accepted.iter().any(|(_, pk_hex, _)| *pk_hex == pubkey_hex);
+            let slot_taken = pending.signatures.keys().any(|pk_hex| {
+                *pk_hex != pubkey_hex && self.principal_of(pk_hex).as_ref() == Some(&principal_hex)
+            }) || accepted
+                .iter()
+                .any(|(slot_hex, pk_hex, _)| *slot_hex == principal_hex && *pk_hex != pubkey_hex);
+            if slot_taken || (repeat && self.duplicate_policy == DuplicatePolicy::Reject) {
+                failures.push((index, MultisigError::DuplicateSignature));
+                continue;
+            }
+
+            match verify_signature_with(algorithm, &message, &signature, &signer_pubkey) {
+                Ok(true) if repeat && self.duplicate_policy == DuplicatePolicy::Ignore => {}
+                Ok(true) => {
+                    accepted.retain(|(_, pk_hex, _)| *pk_hex != pubkey_hex);
+                    accepted.push((
+                        principal_hex,
+                        pubkey_hex,
+                        hex::encode(signature.serialize_compact()),
+                    ));
+                }
+                Ok(false) => failures.push((index, MultisigError::InvalidSignature {
+                    tx_id: tx_id.to_string(),
+                    pubkey_hex,
+                })),
+                Err(e) => failures.push((index, e)),
+            }
+        }
+
+        let mut added = 0;
+        let timestamp = self.now();
+        let pending = self.pending_transactions.get_mut(tx_id).unwrap();
+        for (principal_hex, pubkey_hex, sig_hex) in accepted {
+            pending.rejections.remove(&principal_hex);
+            pending.verified.insert(pubkey_hex.clone());
+            let tx_id = tx_id.to_string();
+            let event = if pending.signatures.insert(pubkey_hex.clone(), sig_hex).is_some() {
+                WalletEvent::SignatureReplaced { tx_id, signer: pubkey_hex, timestamp }
+            } else {
+                added += 1;
+                WalletEvent::SignatureAdded { tx_id, signer: pubkey_hex, timestamp }
+            };
+            self.events.push(event);
+        }
+        self.metrics.signatures_added += added;
+        self.notify_if_ready(tx_id);
+
+        if failures.is_empty() {
+            Ok(added)
+        } else {
+            Err(MultisigError::BatchFailed { added, failures })
+        }
+    }
+    
+    /// Check if a transaction has enough signatures
+    pub fn has_enough_signatures(&self, tx_id: &str) -> Result<bool> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+        
+        Ok(self.meets_policy(pending))
+    }
+
+    /// Check whether a transaction can still collect enough approval to run
+    ///
+    /// Unlike `has_enough_signatures`, which is also false while signatures
+    /// are still arriving, this is false only when the transaction is dead:
+    /// executed, rejected, expired, or left without enough non-rejecting
+    /// authorized signers to meet the policy.
+    pub fn is_executable_reachable(&self, tx_id: &str) -> Result<bool> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        if pending.executed || pending.rejected || pending.is_expired_at(self.now()) {
+            return Ok(false);
+        }
+
+        // Everyone who has signed or may still sign, minus the vetoes
+        let reachable_weight: usize = self.authorized_keys_hex
+            .iter()
+            .filter(|pk_hex| !pending.rejections.contains(*pk_hex))
+            .map(|pk_hex| self.weight_of(pk_hex))
+            .sum();
+        let mandatory_available = self.required_signers.iter().all(|pk_hex| {
+            self.authorized_keys_hex.contains(pk_hex) && !pending.rejections.contains(pk_hex)
+        });
+
+        Ok(reachable_weight >= self.threshold && mandatory_available)
+    }
+    
+    /// How much more signature weight a transaction needs to reach the
+    /// threshold, 0 once it is met
+    ///
+    /// In unweighted wallets this is the number of signatures still needed.
+    /// Signatures from signers who have since been removed do not count.
+    /// Mandatory signers are reported separately by `can_execute`.
+    pub fn signatures_remaining(&self, tx_id: &str) -> Result<usize> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        Ok(self.threshold.saturating_sub(self.signed_weight(pending)))
+    }
+
+    /// Report whether a transaction could be executed right now, and if not
+    /// why, without changing anything
+    ///
+    /// `execute_transaction` runs exactly these checks before executing.
+    /// Fails with `TransactionIdMismatch` if the body changed since it was
+    /// proposed, since its signatures no longer cover it.
+    pub fn can_execute(&self, tx_id: &str) -> Result<ExecutionReadiness> {
+        let Some(pending) = self.pending_transactions.get(tx_id) else {
+            return Ok(ExecutionReadiness::NotFound);
+        };
+        pending.check_body()?;
+
+        let now = self.now();
+        let have = self.signed_weight(pending);
+        let missing = self.missing_required_signers(pending);
+        // Executed is checked first: its signatures may have gone stale
+        // through signer removal since it ran. A rejected or expired
+        // transaction is reported as such before any shortfall, since more
+        // signatures cannot help it.
+        let readiness = if pending.executed {
+            ExecutionReadiness::AlreadyExecuted
+        } else if pending.rejected {
+            ExecutionReadiness::Rejected
+        } else if pending.is_expired_at(now) {
+            ExecutionReadiness::Expired
+        } else if have < self.threshold {
+            ExecutionReadiness::Insufficient { have, need: self.threshold }
+        } else if !missing.is_empty() {
+            ExecutionReadiness::MissingRequiredSigners { missing }
+        } else if !pending.transaction.is_unlocked_at(now) {
+            ExecutionReadiness::NotYetValid {
+                valid_at: pending.transaction.not_before.unwrap_or_default(),
+            }
+        } else {
+            ExecutionReadiness::Ready
+        };
+        Ok(readiness)
+    }
+
+    /// Verify and execute a transaction if it has enough signatures
+    pub fn execute_transaction(&mut self, tx_id: &str) -> Result<Transaction> {
+        match self.can_execute(tx_id)? {
+            ExecutionReadiness::Ready => {}
+            ExecutionReadiness::NotFound => {
+                return Err(MultisigError::TransactionNotFound { tx_id: tx_id.to_string() });
+            }
+            ExecutionReadiness::Insufficient { have, need } => {
+                return Err(MultisigError::InsufficientSignatures {
+                    required: need,
+                    actual: have,
+                });
+            }
+            ExecutionReadiness::MissingRequiredSigners { missing } => {
+                return Err(MultisigError::MissingRequiredSigners { missing });
+            }
+            ExecutionReadiness::AlreadyExecuted => {
+                return Err(MultisigError::TransactionAlreadyExecuted);
+            }
+            ExecutionReadiness::Rejected => return Err(MultisigError::TransactionRejected),
+            ExecutionReadiness::Expired => return Err(MultisigError::TransactionExpired),
+            ExecutionReadiness::NotYetValid { valid_at } => {
+                return Err(MultisigError::TransactionNotYetValid { valid_at });
+            }
+        }
+        
+        let executed_at = self.now();
+        let mut signers: Vec<String> = self.pending_transactions[tx_id]
+            .signatures
+            .keys()
+            .filter(|pk_hex| self.principal_of(pk_hex).is_some())
+            .cloned()
+            .collect();
+        signers.sort();
+        let receipt = ExecutionReceipt {
+            tx_id: tx_id.to_string(),
+            executed_at,
+            signature_count: signers.len(),
+            signers,
+        };
+        self.receipts.insert(tx_id.to_string(), receipt);
+
+        let pending = self.pending_transactions.get_mut(tx_id).unwrap();
+        pending.executed = true;
+        self.executed_ids.insert(tx_id.to_string());
+        self.metrics.executions += 1;
+        self.events.push(WalletEvent::TransactionExecuted {
+            tx_id: tx_id.to_string(),
+            timestamp: executed_at,
+        });
+        
+        Ok(pending.transaction.clone())
+    }
+
+    /// Execute a transaction and return its receipt
+    pub fn execute_with_receipt(&mut self, tx_id: &str) -> Result<ExecutionReceipt> {
+        self.execute_transaction(tx_id)?;
+        Ok(self.receipts[tx_id].clone())
+    }
+
+    /// Record of how and when a transaction was executed
+    pub fn receipt(&self, tx_id: &str) -> Option<&ExecutionReceipt> {
+        self.receipts.get(tx_id)
+    }
+
+    /// Withdraw a proposal that has not been executed
+    pub fn cancel_transaction(&mut self, tx_id: &str) -> Result<()> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        if pending.executed {
+            return Err(MultisigError::TransactionAlreadyExecuted);
+        }
+
+        self.pending_transactions.remove(tx_id);
+        self.events.push(WalletEvent::TransactionCancelled {
+            tx_id: tx_id.to_string(),
+            timestamp: self.now(),
+        });
+        Ok(())
+    }
+
+    /// Record a signer's veto against a pending transaction
+    ///
+    /// A veto withdraws the signer's signature, or their delegate's, if the
+    /// slot was already filled. Once the vetoed weight leaves too little for
+    /// the threshold to ever be met, or a mandatory signer vetoes, the
+    /// transaction is marked rejected and stops accepting signatures.
+    pub fn reject_transaction(&mut self, tx_id: &str, signer_pubkey: &PublicKey) -> Result<()> {
+        let pubkey_hex = hex::encode(signer_pubkey.serialize());
+        if !self.is_authorized(signer_pubkey) {
+            return Err(MultisigError::UnauthorizedSigner { pubkey_hex });
+        }
+
+        let max_rejected_weight = self.total_weight() - self.threshold;
+
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        if pending.executed {
+            return Err(MultisigError::TransactionAlreadyExecuted);
+        }
+
+        if pending.rejected {
+            return Err(MultisigError::TransactionRejected);
+        }
+
+        if pending.rejections.contains(&pubkey_hex) {
+            return Err(MultisigError::DuplicateRejection);
+        }
+
+        let rejected_weight = pending.rejections
+            .iter()
+            .chain(std::iter::once(&pubkey_hex))
+            .map(|pk_hex| self.weight_of(pk_hex))
+            .sum::<usize>();
+        let mandatory = self.required_signers.contains(&pubkey_hex);
+
+        // The veto empties the signer's slot, including a delegate's
+        // signature filling it
+        let withdrawn: Vec<String> = pending.signatures
+            .keys()
+            .filter(|pk_hex| self.principal_of(pk_hex).as_ref() == Some(&pubkey_hex))
+            .cloned()
+            .collect();
+
+        let pending = self.pending_transactions.get_mut(tx_id).unwrap();
+        for pk_hex in &withdrawn {
+            pending.signatures.remove(pk_hex);
+            pending.verified.remove(pk_hex);
+        }
+        pending.rejections.insert(pubkey_hex);
+        self.metrics.rejections += 1;
+
+        if rejected_weight > max_rejected_weight || mandatory {
+            pending.rejected = true;
+        }
+
+        Ok(())
+    }
+    
+    /// Remove unexecuted transactions whose expiry has passed
+    ///
+    /// Returns the number of transactions pruned.
+    pub fn prune_expired(&mut self) -> usize {
+        let now = self.now();
+        let before = self.pending_transactions.len();
+
+        self.pending_transactions
+            .retain(|_, pending| pending.executed || !pending.is_expired_at(now));
+
+        before - self.pending_transactions.len()
+    }
+
+    /// Remove executed transactions, returning how many were removed
+    ///
+    /// Their receipts and ids are kept, so `receipt` still answers for them
+    /// and they can never be proposed again.
+    pub fn prune_executed(&mut self) -> usize {
+        let before = self.pending_transactions.len();
+        self.pending_transactions.retain(|_, pending| !pending.executed);
+        before - self.pending_transactions.len()
+    }
+    
+    /// List the transactions that have not been executed yet
+    pub fn pending_transactions(&self) -> Vec<&Transaction> {
+        self.pending_transactions
+            .values()
+            .filter(|pending| !pending.executed)
+            .map(|pending| &pending.transaction)
+            .collect()
+    }
+
+    /// List the executed transactions not yet removed by `prune_executed`
+    pub fn executed_transactions(&self) -> Vec<&Transaction> {
+        self.pending_transactions
+            .values()
+            .filter(|pending| pending.executed)
+            .map(|pending| &pending.transaction)
+            .collect()
+    }
+
+    /// List unexecuted transactions oldest first
+    ///
+    /// Ties on `timestamp` are broken by `nonce`, then by id, so the order
+    /// is the same on every call.
+    pub fn pending_sorted_by_timestamp(&self) -> Vec<&Transaction> {
+        let mut transactions = self.pending_transactions();
+        transactions.sort_by(|a, b| {
+            (a.timestamp, a.nonce, &a.id).cmp(&(b.timestamp, b.nonce, &b.id))
+        });
+        transactions
+    }
+
+    /// List unexecuted transactions carrying `tag`, oldest first
+    pub fn pending_with_tag(&self, tag: &str) -> Vec<&Transaction> {
+        self.pending_sorted_by_timestamp()
+            .into_iter()
+            .filter(|tx| tx.has_tag(tag))
+            .collect()
+    }
+
+    /// The unexecuted transaction that was created first
+    pub fn oldest_pending(&self) -> Option<&Transaction> {
+        self.pending_sorted_by_timestamp().into_iter().next()
+    }
+
+    /// List the ids of transactions that have not been executed yet
+    pub fn pending_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.pending_transactions
+            .iter()
+            .filter(|(_, pending)| !pending.executed)
+            .map(|(tx_id, _)| tx_id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Sum of the amounts and fees of all unexecuted transactions
+    ///
+    /// Fails with `AmountOverflow` if the total does not fit in a `u128`.
+    pub fn pending_total_amount(&self) -> Result<u128> {
+        checked_total(self.pending_transactions().into_iter())
+    }
+
+    /// Sum of the amounts and fees of unexecuted transactions that have met
+    /// the signature policy, whether or not they are executable right now
+    pub fn pending_total_ready(&self) -> Result<u128> {
+        checked_total(
+            self.pending_transactions
+                .values()
+                .filter(|pending| !pending.executed && self.meets_policy(pending))
+                .map(|pending| &pending.transaction),
+        )
+    }
+
+    /// List the ids of transactions that have enough signatures and can be
+    /// executed right now
+    pub fn transactions_ready_to_execute(&self) -> Vec<String> {
+        let now = self.now();
+        let mut ids: Vec<String> = self.pending_transactions
+            .iter()
+            .filter(|(_, pending)| {
+                !pending.executed
+                    && !pending.rejected
+                    && !pending.is_expired_at(now)
+                    && pending.transaction.is_unlocked_at(now)
+                    && self.meets_policy(pending)
+            })
+            .map(|(tx_id, _)| tx_id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Ids of executable transactions, highest fee first
+    ///
+    /// Equal fees go oldest first, with the same tie-breaks as
+    /// `pending_sorted_by_timestamp`.
+    pub fn ready_by_priority(&self) -> Vec<String> {
+        let mut ready: Vec<&Transaction> = self
+            .transactions_ready_to_execute()
+            .iter()
+            .map(|tx_id| &self.pending_transactions[tx_id].transaction)
+            .collect();
+        ready.sort_by(|a, b| {
+            (std::cmp::Reverse(a.fee), a.timestamp, a.nonce, &a.id)
+                .cmp(&(std::cmp::Reverse(b.fee), b.timestamp, b.nonce, &b.id))
+        });
+        ready.into_iter().map(|tx| tx.id.clone()).collect()
+    }
+
+    /// Try to execute every unexecuted transaction that meets the signature
+    /// policy, oldest first
+    ///
+    /// Each one goes through `execute_transaction`, so time locks, expiry and
+    /// rejection are checked per transaction and a failure does not stop the
+    /// rest. Transactions still short of signatures are not attempted.
+    pub fn execute_all_ready(&mut self) -> Vec<(String, Result<Transaction>)> {
+        let candidates: Vec<String> = self
+            .pending_sorted_by_timestamp()
+            .into_iter()
+            .filter(|tx| self.meets_policy(&self.pending_transactions[&tx.id]))
+            .map(|tx| tx.id.clone())
+            .collect();
+
+        candidates
+            .into_iter()
+            .map(|tx_id| {
+                let outcome = self.execute_transaction(&tx_id);
+                (tx_id, outcome)
+            })
+            .collect()
+    }
+
+    /// Execute the first transaction of `ready_by_priority`
+    ///
+    /// Returns `None` when nothing is ready.
+    pub fn execute_next(&mut self) -> Result<Option<Transaction>> {
+        match self.ready_by_priority().first() {
+            Some(tx_id) => self.execute_transaction(tx_id).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Everything a frontend needs to show a proposal's progress
+    ///
+    /// `signatures_collected` is the combined weight of the signers so far,
+    /// which equals the signature count in unweighted wallets.
+    pub fn transaction_status(&self, tx_id: &str) -> Result<TransactionStatus> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        let signatures_collected = self.signed_weight(pending);
+        let mut signers_done: Vec<String> = pending.signatures.keys().cloned().collect();
+        signers_done.sort();
+
+        let signed = self.signed_principals(pending);
+        let signers_remaining = self.authorized_keys_hex
+            .iter()
+            .filter(|pk_hex| !signed.contains(*pk_hex))
+            .cloned()
+            .collect();
+
+        Ok(TransactionStatus {
+            signatures_collected,
+            signatures_required: self.threshold,
+            is_ready: self.can_execute(tx_id)? == ExecutionReadiness::Ready,
+            is_executed: pending.executed,
+            signers_done,
+            signers_remaining,
+        })
+    }
+
+    /// Stable identifier signatures are bound to
+    ///
+    /// Derived from the initial threshold and signer set; later signer or
+    /// threshold changes do not alter it, so collected signatures stay valid.
+    pub fn wallet_id(&self) -> &str {
+        &self.wallet_id
+    }
+
+    /// Short hex code for people to compare wallet configurations by eye
+    ///
+    /// The first 8 bytes of SHA-256 over the current threshold and the sorted
+    /// signer keys, so it does not depend on the order signers were added.
+    /// Unlike `wallet_id` it follows signer and threshold changes. Signers
+    /// with a weight other than 1 contribute their weight as well.
+    pub fn fingerprint(&self) -> String {
+        let signers: Vec<String> = self.canonical_key_list()
+            .into_iter()
+            .map(|pk_hex| match self.weight_of(&pk_hex) {
+                1 => pk_hex,
+                weight => format!("{}*{}", pk_hex, weight),
+            })
+            .collect();
+        let data = format!("fingerprint:{}:{}", self.threshold, signers.join(","));
+        hex::encode(&hash_message(data.as_bytes())[..8])
+    }
+
+    /// Random challenge for a signer to sign as proof they hold their key
+    ///
+    /// The challenge is a string carrying a domain tag, the wallet
+    /// `fingerprint` and 32 random bytes, so a response can never pass as a
+    /// transaction signature or be replayed against another wallet. Signers
+    /// sign its UTF-8 bytes the way they sign a transaction payload: in their
+    /// own scheme, with the wallet's hash algorithm and message mode.
+    pub fn challenge(&self) -> String {
+        let mut nonce = [0u8; 32];
+        OsRng.fill_bytes(&mut nonce);
+        format!("{}{}:{}", CHALLENGE_TAG, self.fingerprint(), hex::encode(nonce))
+    }
+
+    /// Check that an authorized signer signed a challenge from `challenge`
+    ///
+    /// Returns `false` for an invalid signature, one in a scheme the signer
+    /// does not use, a key outside the wallet, or a challenge issued for a
+    /// different wallet configuration. Tracking which challenges were issued
+    /// and are still unused is up to the caller.
+    pub fn verify_challenge_response(
+        &self,
+        challenge: &str,
+        signature: impl Into<StoredSignature>,
+        pubkey: &PublicKey,
+    ) -> Result<bool> {
+        let signature = signature.into();
+        let bound_here = challenge
+            .strip_prefix(CHALLENGE_TAG)
+            .and_then(|rest| rest.split_once(':'))
+            .is_some_and(|(fingerprint, _)| fingerprint == self.fingerprint());
+        if !bound_here
+            || !self.is_authorized(pubkey)
+            || signature.scheme() != self.signer_scheme(pubkey)
+        {
+            return Ok(false);
+        }
+
+        let (algorithm, message) = self.frame_message(challenge.as_bytes().to_vec());
+        signature.verify(algorithm, &message, pubkey)
+    }
+
+    /// Sum of all authorized keys as a single curve point
+    ///
+    /// This is plain point addition, not MuSig: it gives the wallet one
+    /// public identity but is not safe to sign for against rogue-key attacks.
+    pub fn aggregated_pubkey(&self) -> Result<PublicKey> {
+        let keys = self.canonical_key_list()
+            .iter()
+            .map(|pk_hex| parse_pubkey_hex(pk_hex))
+            .collect::<Result<Vec<PublicKey>>>()?;
+        Ok(PublicKey::combine_keys(&keys.iter().collect::<Vec<_>>())?)
+    }
+
+    /// Hex of the compressed aggregate key
+    pub fn wallet_address(&self) -> Result<String> {
+        Ok(hex::encode(self.aggregated_pubkey()?.serialize()))
+    }
+
+    /// The wallet's audit log, oldest event first
+    pub fn events(&self) -> &[WalletEvent] {
+        &self.events
+    }
+
+    /// Write the audit log as JSON Lines, one event per line
+    ///
+    /// Events are serialized straight into `writer` one at a time, so the
+    /// whole log is never held as a single string. Wrap files or sockets in a
+    /// `BufWriter`; the writer is flushed once at the end.
+    pub fn export_events_jsonl(&self, writer: &mut impl Write) -> Result<()> {
+        for event in &self.events {
+            serde_json::to_writer(&mut *writer, event)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read an event history written by `export_events_jsonl`
+    ///
+    /// Input is consumed line by line; blank lines are skipped and the first
+    /// malformed line fails with `SerializationError`.
+    pub fn import_events_jsonl(reader: impl BufRead) -> Result<Vec<WalletEvent>> {
+        let mut events = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            events.push(serde_json::from_str(&line)?);
+        }
+        Ok(events)
+    }
+
+    /// Number of unexecuted transactions each authorized signer has signed,
+    /// keyed by pubkey hex
+    ///
+    /// Every authorized signer appears, with 0 if they have signed nothing.
+    pub fn signer_activity(&self) -> HashMap<String, usize> {
+        let mut activity: HashMap<String, usize> = self.authorized_keys_hex
+            .iter()
+            .map(|pk_hex| (pk_hex.clone(), 0))
+            .collect();
+        for pending in self.pending_transactions.values().filter(|pending| !pending.executed) {
+            for pk_hex in self.signed_principals(pending) {
+                if let Some(count) = activity.get_mut(&pk_hex) {
+                    *count += 1;
+                }
+            }
+        }
+        activity
+    }
+
+    /// The `n` signers with the fewest signatures in `signer_activity`,
+    /// fewest first and ties broken by pubkey hex
+    pub fn least_active_signers(&self, n: usize) -> Vec<String> {
+        let mut activity: Vec<(String, usize)> = self.signer_activity().into_iter().collect();
+        activity.sort_by(|(a_hex, a), (b_hex, b)| (a, a_hex).cmp(&(b, b_hex)));
+        activity.into_iter().take(n).map(|(pk_hex, _)| pk_hex).collect()
+    }
+
+    /// Public keys of the signers who have already signed a transaction
+    pub fn signers_for(&self, tx_id: &str) -> Result<Vec<PublicKey>> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        let mut signers_hex: Vec<&String> = pending.signatures.keys().collect();
+        signers_hex.sort();
+
+        signers_hex
+            .into_iter()
+            .map(|pk_hex| parse_pubkey_hex(pk_hex))
+            .collect()
+    }
+
+    /// Parsed ECDSA signatures collected for a transaction, sorted by signer
+    ///
+    /// Stored entries that no longer parse indicate corruption and fail with
+    /// `CryptoError` or `MalformedSignature`. A stored Schnorr signature
+    /// gives `SignatureSchemeMismatch`.
+    pub fn collected_signatures(&self, tx_id: &str) -> Result<Vec<(PublicKey, Signature)>> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        let mut entries: Vec<(&String, &String)> = pending.signatures.iter().collect();
+        entries.sort();
+
+        entries
+            .into_iter()
+            .map(|(pk_hex, sig)| {
+                let pubkey = parse_pubkey_hex(pk_hex).map_err(|_| {
+                    MultisigError::CryptoError(format!("corrupt signer key {}", pk_hex))
+                })?;
+                match StoredSignature::decode(sig)? {
+                    StoredSignature::Ecdsa(signature) => Ok((pubkey, signature)),
+                    StoredSignature::Schnorr(_) => Err(MultisigError::SignatureSchemeMismatch),
+                }
+            })
+            .collect()
+    }
+
+    /// Authorized public keys that have not signed a transaction yet,
+    /// either themselves or through their delegate
+    pub fn pending_signers(&self, tx_id: &str) -> Result<Vec<PublicKey>> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        let signed = self.signed_principals(pending);
+        Ok(self.authorized_keys
+            .iter()
+            .zip(&self.authorized_keys_hex)
+            .filter(|(_, pk_hex)| !signed.contains(*pk_hex))
+            .map(|(pk, _)| *pk)
+            .collect())
+    }
+
+    /// Pack a fully signed transaction with just enough signatures to meet
+    /// the threshold
+    ///
+    /// Mandatory signers' signatures are always included; the rest are taken
+    /// in pubkey-hex order until their combined weight reaches the threshold.
+    pub fn finalize(&self, tx_id: &str) -> Result<SignedTransaction> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        let actual = self.signed_weight(pending);
+        if actual < self.threshold {
+            return Err(MultisigError::InsufficientSignatures {
+                required: self.threshold,
+                actual,
+            });
+        }
+
+        let missing = self.missing_required_signers(pending);
+        if !missing.is_empty() {
+            return Err(MultisigError::MissingRequiredSigners { missing });
+        }
+
+        // Mandatory signatures always go in, ahead of the rest
+        let mut available: Vec<(String, &String, &String)> = pending.signatures
+            .iter()
+            .filter_map(|(pk_hex, sig)| Some((self.principal_of(pk_hex)?, pk_hex, sig)))
+            .collect();
+        available.sort_by(|(a_slot, a_hex, _), (b_slot, b_hex, _)| {
+            (!self.required_signers.contains(a_slot), a_hex)
+                .cmp(&(!self.required_signers.contains(b_slot), b_hex))
+        });
+
+        let mut signatures = Vec::new();
+        let mut weight = 0;
+        for (slot_hex, pk_hex, sig) in available {
+            if weight >= self.threshold && !self.required_signers.contains(&slot_hex) {
+                break;
+            }
+            weight += self.weight_of(&slot_hex);
+            signatures.push((pk_hex.clone(), sig.clone()));
+        }
+
+        Ok(SignedTransaction {
+            transaction: pending.transaction.clone(),
+            signatures,
+        })
+    }
+
+    /// Re-verify every signature in a bundle against this wallet's signers
+    ///
+    /// Returns `false` if any signature is malformed, invalid, repeated or
+    /// from an unauthorized key, or if together they fall short of the
+    /// policy.
+    ///
+    /// Signatures this wallet already verified for the same, unchanged
+    /// pending transaction are not verified again.
+    pub fn verify_signed_transaction(&self, bundle: &SignedTransaction) -> Result<bool> {
+        let (algorithm, message) = self.signed_message(&bundle.transaction);
+        let bundle_hash = hex::encode(bundle.transaction.signing_hash());
+        let cached = self.pending_transactions
+            .get(&bundle.transaction.id)
+            .filter(|pending| pending.body_hash == bundle_hash);
+        let mut seen = HashSet::new();
+        let mut weight = 0;
+
+        for (pk_hex, sig) in &bundle.signatures {
+            let (Ok(signer_pubkey), Ok(signature)) =
+                (parse_pubkey_hex(pk_hex), StoredSignature::decode(sig))
+            else {
+                return Ok(false);
+            };
+
+            let canonical_hex = hex::encode(signer_pubkey.serialize());
+            let Some(principal_hex) = self.principal_of(&canonical_hex) else {
+                return Ok(false);
+            };
+            if signature.scheme() != self.scheme_of(&canonical_hex)
+                || !seen.insert(principal_hex.clone())
+            {
+                return Ok(false);
+            }
+
+            let known_good =
+                cached.is_some_and(|pending| pending.is_verified(&canonical_hex, sig));
+            if !known_good && !signature.verify(algorithm, &message, &signer_pubkey)? {
+                return Ok(false);
+            }
+            weight += self.weight_of(&principal_hex);
+        }
+
+        let mandatory_present = self.required_signers.iter().all(|pk_hex| seen.contains(pk_hex));
+        Ok(weight >= self.threshold && mandatory_present)
+    }
+
+    /// Verify every stored signature of a pending transaction from scratch,
+    /// ignoring cached results
+    ///
+    /// Returns `false` if the body changed since proposal or any signature
+    /// no longer verifies.
+    pub fn reverify_all(&self, tx_id: &str) -> Result<bool> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        if pending.check_body().is_err() {
+            return Ok(false);
+        }
+
+        let (algorithm, message) = self.signed_message(&pending.transaction);
+        for (pk_hex, sig) in &pending.signatures {
+            let signer_pubkey = parse_pubkey_hex(pk_hex)?;
+            if !StoredSignature::decode(sig)?.verify(algorithm, &message, &signer_pubkey)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Bundle a pending transaction and its signatures into a base64 blob
+    /// that other signers can import
+    pub fn export_pending(&self, tx_id: &str) -> Result<String> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        let export = PendingExport {
+            transaction: pending.transaction.clone(),
+            signatures: pending.signatures.clone(),
+        };
+        Ok(STANDARD.encode(serde_json::to_vec(&export)?))
+    }
+
+    /// Merge the signatures from an `export_pending` blob into the matching
+    /// local pending transaction
+    ///
+    /// Each signature is verified against the local copy of the transaction.
+    /// Signers whose slot is already filled locally, by them or their
+    /// delegate, are skipped. Returns the number of signatures added. A
+    /// signer key that does not parse fails with `InvalidPublicKey`, indexed
+    /// by its position among the blob's signers in sorted order.
+    pub fn import_pending(&mut self, blob: &str) -> Result<usize> {
+        let bytes = STANDARD.decode(blob.trim())?;
+        let export: PendingExport = serde_json::from_slice(&bytes)?;
+        let tx_id = export.transaction.id;
+
+        let pending = self.pending_transactions
+            .get(&tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.clone() })?;
+
+        let filled = self.signed_principals(pending);
+        let mut exported: Vec<_> = export.signatures.into_iter().collect();
+        exported.sort();
+
+        let mut added = 0;
+        for (index, (pk_hex, sig)) in exported.into_iter().enumerate() {
+            if self.fills_slot(&pk_hex, &filled) {
+                continue;
+            }
+            let signer_pubkey = parse_pubkey_hex(&pk_hex)
+                .map_err(|_| MultisigError::InvalidPublicKey { index: Some(index) })?;
+            let signature = StoredSignature::decode(&sig)?;
+            // A principal and its delegate may both be in the export
+            match self.insert_signature(&tx_id, signature, &signer_pubkey, DuplicatePolicy::Reject) {
+                Ok(()) => added += 1,
+                Err(MultisigError::DuplicateSignature) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(added)
+    }
+
+    /// Copy signatures for `tx_id` from another copy of this wallet
+    ///
+    /// Every signature is re-verified against this wallet's signers and its
+    /// own copy of the transaction. Signatures for a slot that is already
+    /// filled, by the signer or their delegate, and signatures that fail
+    /// verification are skipped. Returns the number of signatures added.
+    pub fn merge_signatures(&mut self, tx_id: &str, other: &MultisigWallet) -> Result<usize> {
+        let theirs = other.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+        let ours = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+
+        let filled = self.signed_principals(ours);
+        let mut candidates: Vec<_> = theirs.signatures
+            .iter()
+            .filter(|(pk_hex, _)| !self.fills_slot(pk_hex, &filled))
+            .collect();
+        candidates.sort();
+
+        let mut added = 0;
+        for (pk_hex, sig) in candidates {
+            let (Ok(signer_pubkey), Ok(signature)) =
+                (parse_pubkey_hex(pk_hex), StoredSignature::decode(sig))
+            else {
+                continue;
+            };
+
+            match self.insert_signature(tx_id, signature, &signer_pubkey, DuplicatePolicy::Reject) {
+                Ok(()) => added += 1,
+                Err(MultisigError::InvalidSignature { .. })
+                | Err(MultisigError::DuplicateSignature)
+                | Err(MultisigError::UnauthorizedSigner { .. })
+                | Err(MultisigError::SignatureSchemeMismatch)
+                | Err(MultisigError::NonCanonicalSignature) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(added)
+    }
+
+    /// Serialize the whole wallet state as pretty-printed JSON
+    ///
+    /// The output holds the threshold, signer keys as hex, and every pending
+    /// transaction with its signatures and execution status.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Rebuild a wallet from `to_json` output, including its parsed keys
+    pub fn from_json(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Encode the wallet in the compact bincode format
+    ///
+    /// Holds the same state as `to_json` in a fraction of the space, for
+    /// storing many wallets in a database. The encoding is positional, so it
+    /// is only readable by a build with the same wallet fields.
+    #[cfg(feature = "bincode")]
+    pub fn to_bincode(&self) -> Result<Vec<u8>> {
+        bincode::serialize(self).map_err(|e| MultisigError::BincodeError(e.to_string()))
+    }
+
+    /// Rebuild a wallet from `to_bincode` output, including its parsed keys
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self> {
+        bincode::deserialize(bytes).map_err(|e| MultisigError::BincodeError(e.to_string()))
+    }
+
+    /// Save the wallet to a JSON file
+    pub fn save_to_file(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Load a wallet previously written by `save_to_file`
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Rebuild `authorized_keys` from their hex encoding, which is the only
+    /// form that gets serialized
+    fn restore_authorized_keys(&mut self) -> Result<()> {
+        self.authorized_keys = parse_pubkey_list(&self.authorized_keys_hex)?;
+        Ok(())
+    }
+    
+    /// Check whether a hex-encoded key, compressed or uncompressed, belongs
+    /// to an authorized signer
+    pub fn is_authorized_hex(&self, pubkey_hex: &str) -> bool {
+        parse_pubkey_hex(pubkey_hex).is_ok_and(|pubkey| self.is_authorized(&pubkey))
+    }
+
+    /// Check whether a public key belongs to an authorized signer
+    pub fn is_authorized_key(&self, pubkey: &PublicKey) -> bool {
+        self.is_authorized(pubkey)
+    }
+
+    /// The authorized signer keys, in the order they were added
+    pub fn authorized_keys(&self) -> Vec<PublicKey> {
+        self.authorized_keys.clone()
+    }
+
+    /// Compressed hex of the authorized signer keys, in the order they were
+    /// added
+    pub fn authorized_keys_hex(&self) -> &[String] {
+        &self.authorized_keys_hex
+    }
+
+    /// Compressed hex of the authorized signer keys, sorted
+    ///
+    /// Wallets with the same signers give the same list whatever order the
+    /// keys were added in, which keeps exported configs diffable.
+    pub fn canonical_key_list(&self) -> Vec<String> {
+        let mut keys = self.authorized_keys_hex.clone();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Check if a public key is authorized
+    ///
+    /// Signer keys, signatures and pubkey hexes are all public, so this and
+    /// the duplicate-signature lookups use ordinary comparisons. Anything
+    /// involving secret keys goes through `secret_key_matches_pubkey`.
+    fn is_authorized(&self, pubkey: &PublicKey) -> bool {
+        self.authorized_keys.iter().any(|pk| pk == pubkey)
+    }
+
+    /// Voting weight of a signer, defaulting to 1 for wallets saved before
+    /// weights existed
+    fn weight_of(&self, pubkey_hex: &str) -> usize {
+        self.weights.get(pubkey_hex).copied().unwrap_or(1) as usize
+    }
+
+    /// Combined weight of every authorized signer
+    fn total_weight(&self) -> usize {
+        self.authorized_keys_hex
+            .iter()
+            .map(|pk_hex| self.weight_of(pk_hex))
+            .sum()
+    }
+
+    /// Fail if adding `additional` transactions would exceed `max_pending`
+    fn check_capacity(&self, additional: usize) -> Result<()> {
+        match self.max_pending {
+            Some(limit) if self.open_count() + additional > limit => {
+                Err(MultisigError::PendingLimitReached { limit })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Transactions that have not been executed yet
+    fn open_count(&self) -> usize {
+        self.pending_transactions.values().filter(|p| !p.executed).count()
+    }
+
+    /// Mandatory signers who have not signed a pending transaction yet
+    fn missing_required_signers(&self, pending: &PendingTransaction) -> Vec<String> {
+        let signed = self.signed_principals(pending);
+        self.required_signers
+            .iter()
+            .filter(|pk_hex| !signed.contains(*pk_hex))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether a pending transaction's signatures satisfy the wallet policy
+    fn meets_policy(&self, pending: &PendingTransaction) -> bool {
+        self.signed_weight(pending) >= self.threshold
+            && self.missing_required_signers(pending).is_empty()
+    }
+
+    /// Combined weight of the signers who signed a pending transaction
+    ///
+    /// Only signers still in the authorized set count, so a stale entry
+    /// from a removed signer can never help reach the threshold.
+    fn signed_weight(&self, pending: &PendingTransaction) -> usize {
+        self.signed_principals(pending)
+            .iter()
+            .map(|pk_hex| self.weight_of(pk_hex))
+            .sum()
+    }
+
+    /// Authorized signers whose slot is filled on a pending transaction,
+    /// either by their own signature or their delegate's
+    fn signed_principals(&self, pending: &PendingTransaction) -> HashSet<String> {
+        pending.signatures
+            .keys()
+            .filter_map(|pk_hex| self.principal_of(pk_hex))
+            .collect()
+    }
+
+    /// Whether a key's signature would go to a slot that is already in
+    /// `filled`, by the key itself, its principal or its delegate
+    fn fills_slot(&self, pubkey_hex: &str, filled: &HashSet<String>) -> bool {
+        self.principal_of(pubkey_hex).is_some_and(|principal_hex| filled.contains(&principal_hex))
+    }
+
+    /// The authorized signer whose slot a key's signature fills: the key
+    /// itself, or the principal it is a delegate for
+    fn principal_of(&self, pubkey_hex: &str) -> Option<String> {
+        if self.authorized_keys_hex.iter().any(|pk_hex| pk_hex == pubkey_hex) {
+            return Some(pubkey_hex.to_string());
+        }
+        self.delegations
+            .iter()
+            .find(|(principal_hex, delegate_hex)| {
+                *delegate_hex == pubkey_hex && self.authorized_keys_hex.contains(principal_hex)
+            })
+            .map(|(principal_hex, _)| principal_hex.clone())
+    }
+
+    /// Scheme a key signs with: its own if set, otherwise the wallet's
+    fn scheme_of(&self, pubkey_hex: &str) -> SignatureScheme {
+        self.signer_schemes.get(pubkey_hex).copied().unwrap_or(self.scheme)
+    }
+
+    /// Whether a key is some principal's delegate
+    fn is_delegate(&self, pubkey_hex: &str) -> bool {
+        self.delegations.values().any(|delegate_hex| delegate_hex == pubkey_hex)
+    }
+    
+    /// Get the number of signatures for a transaction
+    pub fn get_signature_count(&self, tx_id: &str) -> Result<usize> {
+        let pending = self.pending_transactions
+            .get(tx_id)
+            .ok_or_else(|| MultisigError::TransactionNotFound { tx_id: tx_id.to_string() })?;
+        
+        Ok(pending.signatures.len())
+    }
+    
+    /// Counts of wallet operations since the wallet was created
+    pub fn metrics(&self) -> WalletMetrics {
+        self.metrics
+    }
+
+    /// Get wallet information
+    pub fn info(&self) -> WalletInfo {
+        WalletInfo {
+            threshold: self.threshold,
+            total_signers: self.total_signers,
+            total_weight: self.total_weight(),
+            pending_count: self.pending_transactions.len(),
+        }
+    }
+
+    /// Wallet information along with how many transactions are ready to
+    /// execute and how many have executed
+    pub fn info_detailed(&self) -> WalletInfoDetailed {
+        let executed_count = self.pending_transactions
+            .values()
+            .filter(|pending| pending.executed)
+            .count();
+        let ready_count = self.pending_transactions
+            .values()
+            .filter(|pending| !pending.executed && self.meets_policy(pending))
+            .count();
+
+        WalletInfoDetailed {
+            threshold: self.threshold,
+            total_signers: self.total_signers,
+            total_weight: self.total_weight(),
+            pending_count: self.pending_transactions.len(),
+            ready_count,
+            executed_count,
+        }
+    }
+
+    /// Advisories about the current signer configuration
+    ///
+    /// Empty for a well-formed M-of-N wallet with 1 < M < N.
+    pub fn security_warnings(&self) -> Vec<WalletWarning> {
+        let mut warnings = Vec::new();
+        if self.threshold == 1 {
+            warnings.push(WalletWarning::ThresholdOfOne);
+        }
+        if self.threshold == self.total_weight() {
+            warnings.push(WalletWarning::ThresholdEqualsTotal);
+        }
+        if self.total_signers == 1 {
+            warnings.push(WalletWarning::SingleSignerRemaining);
+        }
+        warnings
+    }
+}
+
+
+/// A signature of either scheme, in the form it is verified and stored
+///
+/// ECDSA signatures are stored as plain compact hex, as they always have
+/// been. Schnorr signatures carry a `schnorr:` prefix so the stored map
+/// records which verification path applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoredSignature {
+    Ecdsa(Signature),
+    Schnorr(schnorr::Signature),
+}
+
+impl From<Signature> for StoredSignature {
+    fn from(signature: Signature) -> Self {
+        StoredSignature::Ecdsa(signature)
+    }
+}
+
+impl From<schnorr::Signature> for StoredSignature {
+    fn from(signature: schnorr::Signature) -> Self {
+        StoredSignature::Schnorr(signature)
+    }
+}
+
+impl StoredSignature {
+    const SCHNORR_PREFIX: &'static str = "schnorr:";
+
+    fn scheme(&self) -> SignatureScheme {
+        match self {
+            StoredSignature::Ecdsa(_) => SignatureScheme::Ecdsa,
+            StoredSignature::Schnorr(_) => SignatureScheme::Schnorr,
+        }
+    }
+
+    fn verify(
+        &self,
+        algorithm: HashAlgorithm,
+        message: &[u8],
+        signer_pubkey: &PublicKey,
+    ) -> Result<bool> {
+        match self {
+            StoredSignature::Ecdsa(sig) => {
+                verify_signature_with(algorithm, message, sig, signer_pubkey)
+            }
+            StoredSignature::Schnorr(sig) => {
+                let (x_only, _) = signer_pubkey.x_only_public_key();
+                verify_signature_schnorr_with(algorithm, message, sig, &x_only)
+            }
+        }
+    }
+
+    fn encode(&self) -> String {
+        match self {
+            StoredSignature::Ecdsa(sig) => hex::encode(sig.serialize_compact()),
+            StoredSignature::Schnorr(sig) => {
+                format!("{}{}", Self::SCHNORR_PREFIX, hex::encode(sig.serialize()))
+            }
+        }
+    }
+
+    /// Parse a signature previously produced by `encode`
+    fn decode(encoded: &str) -> Result<Self> {
+        let (hex_part, schnorr) = match encoded.strip_prefix(Self::SCHNORR_PREFIX) {
+            Some(rest) => (rest, true),
+            None => (encoded, false),
+        };
+        let bytes = hex::decode(hex_part)
+            .map_err(|e| MultisigError::CryptoError(e.to_string()))?;
+
+        if schnorr {
+            Ok(StoredSignature::Schnorr(schnorr::Signature::from_slice(&bytes)?))
+        } else {
+            Ok(StoredSignature::Ecdsa(Signature::from_compact(&bytes)?))
+        }
+    }
+}
+
+/// Hash the threshold and sorted signer keys into a wallet identifier
+pub(crate) fn compute_wallet_id(threshold: usize, authorized_keys_hex: &[String]) -> String {
+    let mut keys: Vec<&str> = authorized_keys_hex.iter().map(String::as_str).collect();
+    keys.sort_unstable();
+    let data = format!("{}:{}", threshold, keys.join(","));
+    hex::encode(hash_message(data.as_bytes()))
+}
+
+/// Add up transaction costs, failing instead of wrapping on overflow
+fn checked_total<'a>(mut transactions: impl Iterator<Item = &'a Transaction>) -> Result<u128> {
+    transactions.try_fold(0u128, |total, tx| {
+        total.checked_add(tx.total_cost()?).ok_or(MultisigError::AmountOverflow)
+    })
+}
+
+/// Parse a hex-encoded secp256k1 public key in either encoding
+///
+/// The wallet re-serializes parsed keys compressed, so both forms of the
+/// same key end up with one internal hex representation.
+fn parse_pubkey_hex(pk_hex: &str) -> Result<PublicKey> {
+    let bytes = hex::decode(pk_hex).map_err(|_| MultisigError::InvalidPublicKey { index: None })?;
+    PubkeyEncoding::detect(&bytes)?;
+    Ok(PublicKey::from_slice(&bytes)?)
+}
+
+/// Parse a list of hex-encoded public keys, naming the position of the
+/// first one that fails in `InvalidPublicKey`
+fn parse_pubkey_list<'a>(keys_hex: impl IntoIterator<Item = &'a String>) -> Result<Vec<PublicKey>> {
+    keys_hex
+        .into_iter()
+        .enumerate()
+        .map(|(index, pk_hex)| {
+            parse_pubkey_hex(pk_hex)
+                .map_err(|_| MultisigError::InvalidPublicKey { index: Some(index) })
+        })
+        .collect()
+}
+
+impl Serialize for MultisigWallet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        MultisigWallet::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MultisigWallet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let mut wallet = MultisigWallet::deserialize(deserializer)?;
+        wallet.restore_authorized_keys().map_err(serde::de::Error::custom)?;
+
+        // Wallets saved before ids existed get one derived from their
+        // current configuration
+        if wallet.wallet_id.is_empty() {
+            wallet.wallet_id = compute_wallet_id(wallet.threshold, &wallet.authorized_keys_hex);
+        }
+
+        // Likewise for transactions saved before body hashes were recorded
+        for pending in wallet.pending_transactions.values_mut() {
+            if pending.body_hash.is_empty() {
+                pending.body_hash = hex::encode(pending.transaction.signing_hash());
+            }
+        }
+        Ok(wallet)
+    }
+}
+
+/// Operation counters, saved with the wallet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletMetrics {
+    pub proposals: usize,
+    pub signatures_added: usize,
+    pub executions: usize,
+    pub rejections: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct WalletInfo {
     pub threshold: usize,
     pub total_signers: usize,
+    pub total_weight: usize,
+    pub pending_count: usize,
+}
+
+/// `WalletInfo` with readiness counts, returned by `info_detailed`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WalletInfoDetailed {
+    pub threshold: usize,
+    pub total_signers: usize,
+    pub total_weight: usize,
     pub pending_count: usize,
+    /// Unexecuted transactions whose signatures satisfy the policy
+    pub ready_count: usize,
+    pub executed_count: usize,
+}
+
+/// Proof that a transaction was executed, kept by the wallet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionReceipt {
+    pub tx_id: String,
+    /// Unix time (seconds) of execution
+    pub executed_at: u64,
+    /// Hex keys of the authorized signers whose signatures were counted,
+    /// sorted
+    pub signers: Vec<String>,
+    pub signature_count: usize,
+}
+
+/// How `add_signature` handles a signer signing the same transaction again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DuplicatePolicy {
+    /// Fail with `DuplicateSignature`
+    #[default]
+    Reject,
+    /// Return `Ok` and keep the first signature, for idempotent submission
+    Ignore,
+    /// Overwrite the earlier signature, as `update_signature` does
+    Replace,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::crypto::generate_keypair;
+/// Outcome of `can_execute`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExecutionReadiness {
+    Ready,
+    /// Signed weight is below the threshold
+    Insufficient { have: usize, need: usize },
+    /// The threshold is met but these mandatory signers have not signed
+    MissingRequiredSigners { missing: Vec<String> },
+    AlreadyExecuted,
+    /// Enough signers vetoed the transaction
+    Rejected,
+    Expired,
+    /// The transaction is time-locked until `valid_at`
+    NotYetValid { valid_at: u64 },
+    NotFound,
+}
+
+/// A transaction together with enough signatures to execute it, produced
+/// by `finalize`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    pub transaction: Transaction,
+    /// `(pubkey hex, encoded signature)` pairs
+    pub signatures: Vec<(String, String)>,
+}
+
+/// Progress of a single proposal, returned by `transaction_status`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionStatus {
+    pub signatures_collected: usize,
+    pub signatures_required: usize,
+    pub is_ready: bool,
+    pub is_executed: bool,
+    /// Pubkey hexes of signers who have signed, sorted
+    pub signers_done: Vec<String>,
+    /// Pubkey hexes of authorized signers yet to sign, in wallet order
+    pub signers_remaining: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{context, generate_keypair, verify_signature};
+    use crate::crypto::tests::high_s_form;
+    use crate::clock::FixedClock;
+    use crate::transaction::current_timestamp;
+
+    #[test]
+    fn test_wallet_creation() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        
+        let wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]);
+        assert!(wallet.is_ok());
+        
+        let wallet = wallet.unwrap();
+        assert_eq!(wallet.threshold, 2);
+        assert_eq!(wallet.total_signers, 3);
+    }
+
+    #[test]
+    fn test_invalid_threshold() {
+        let (_, pk1) = generate_keypair().unwrap();
+        
+        let wallet = MultisigWallet::new(2, vec![pk1]);
+        assert!(wallet.is_err());
+    }
+
+    #[test]
+    fn test_add_signature_from_bytes() {
+        use sha2::{Digest, Sha256};
+
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        // Signed the way an external device would, from the payload alone
+        let digest: [u8; 32] = Sha256::digest(tx.signing_payload(wallet.wallet_id())).into();
+        let compact = secp256k1::Secp256k1::signing_only()
+            .sign_ecdsa(&secp256k1::Message::from_digest(digest), &sk1)
+            .serialize_compact();
+
+        assert!(matches!(
+            wallet.add_signature_from_bytes(&tx.id, &compact[..63], &pk1),
+            Err(MultisigError::InvalidSignature { .. })
+        ));
+        assert!(matches!(
+            wallet.add_signature_from_bytes(&tx.id, &compact, &pk2),
+            Err(MultisigError::InvalidSignature { .. })
+        ));
+
+        wallet.add_signature_from_bytes(&tx.id, &compact, &pk1).unwrap();
+        assert!(wallet.execute_transaction(&tx.id).is_ok());
+    }
+
+    #[test]
+    fn test_transaction_flow() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+        
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        let tx_id = tx.id.clone();
+        
+        wallet.propose_transaction(tx.clone()).unwrap();
+        
+        // Add first signature
+        let sig1 = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx_id, sig1, &pk1).unwrap();
+        
+        assert!(!wallet.has_enough_signatures(&tx_id).unwrap());
+        
+        // Add second signature
+        let sig2 = tx.sign(&sk2, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx_id, sig2, &pk2).unwrap();
+        
+        assert!(wallet.has_enough_signatures(&tx_id).unwrap());
+        
+        // Execute transaction
+        let executed = wallet.execute_transaction(&tx_id);
+        assert!(executed.is_ok());
+    }
+
+    #[test]
+    fn test_add_and_remove_signer() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        wallet.add_signer(pk3).unwrap();
+        assert_eq!(wallet.info().total_signers, 3);
+        assert!(matches!(wallet.add_signer(pk3), Err(MultisigError::DuplicateSigner)));
+
+        wallet.remove_signer(&pk1).unwrap();
+        assert_eq!(wallet.info().total_signers, 2);
+        assert!(!wallet.is_authorized(&pk1));
+        assert_eq!(wallet.authorized_keys_hex.len(), 2);
+
+        // Dropping to one signer would break the 2-of-N threshold
+        assert!(matches!(
+            wallet.remove_signer(&pk2),
+            Err(MultisigError::InvalidThreshold { m: 2, n: 1 })
+        ));
+        assert!(matches!(wallet.remove_signer(&pk1), Err(MultisigError::UnauthorizedSigner { .. })));
+    }
+
+    #[test]
+    fn test_security_warnings() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+
+        let wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+        assert!(wallet.security_warnings().is_empty());
+
+        let wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        assert_eq!(wallet.security_warnings(), vec![WalletWarning::ThresholdOfOne]);
+
+        let wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+        assert_eq!(wallet.security_warnings(), vec![WalletWarning::ThresholdEqualsTotal]);
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        wallet.remove_signer(&pk2).unwrap();
+        assert_eq!(
+            wallet.security_warnings(),
+            vec![
+                WalletWarning::ThresholdOfOne,
+                WalletWarning::ThresholdEqualsTotal,
+                WalletWarning::SingleSignerRemaining,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rotate_signer() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (sk3, pk3) = generate_keypair().unwrap();
+        let (_, outsider) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        let tx_id = tx.id.clone();
+        wallet.propose_transaction(tx.clone()).unwrap();
+        let sig1 = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx_id, sig1, &pk1).unwrap();
+        let sig2 = tx.sign(&sk2, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx_id, sig2, &pk2).unwrap();
+        assert!(wallet.has_enough_signatures(&tx_id).unwrap());
+
+        assert!(matches!(
+            wallet.rotate_signer(&outsider, pk3),
+            Err(MultisigError::UnauthorizedSigner { .. })
+        ));
+        assert!(matches!(wallet.rotate_signer(&pk1, pk2), Err(MultisigError::DuplicateSigner)));
+
+        // The rotated-out signature no longer counts
+        wallet.rotate_signer(&pk1, pk3).unwrap();
+        assert_eq!(wallet.authorized_keys(), vec![pk3, pk2]);
+        assert_eq!(wallet.signers_for(&tx_id).unwrap(), vec![pk2]);
+        assert!(!wallet.has_enough_signatures(&tx_id).unwrap());
+        assert!(wallet.reverify_all(&tx_id).unwrap());
+
+        let sig1 = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        assert!(matches!(
+            wallet.add_signature(&tx_id, sig1, &pk1),
+            Err(MultisigError::UnauthorizedSigner { .. })
+        ));
+        let sig3 = tx.sign(&sk3, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx_id, sig3, &pk3).unwrap();
+        assert!(wallet.execute_transaction(&tx_id).is_ok());
+    }
+
+    #[test]
+    fn test_remove_signer_drops_pending_signatures() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let pending_tx = Transaction::new("recipient".to_string(), 100, None);
+        let executed_tx = Transaction::new("recipient".to_string(), 200, None);
+        wallet.propose_transaction(pending_tx.clone()).unwrap();
+        wallet.propose_transaction(executed_tx.clone()).unwrap();
+
+        for tx in [&pending_tx, &executed_tx] {
+            wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+            wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        }
+        wallet.execute_transaction(&executed_tx.id).unwrap();
+
+        wallet.remove_signer(&pk1).unwrap();
+
+        // The unexecuted transaction loses the removed signer's signature
+        assert_eq!(wallet.get_signature_count(&pending_tx.id).unwrap(), 1);
+        assert!(!wallet.has_enough_signatures(&pending_tx.id).unwrap());
+
+        // The executed transaction stays executed and cannot run again
+        assert_eq!(wallet.get_signature_count(&executed_tx.id).unwrap(), 2);
+        assert!(matches!(
+            wallet.execute_transaction(&executed_tx.id),
+            Err(MultisigError::TransactionAlreadyExecuted)
+        ));
+    }
+
+    #[test]
+    fn test_set_threshold() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        assert!(matches!(wallet.set_threshold(0), Err(MultisigError::InvalidThreshold { .. })));
+        assert!(matches!(
+            wallet.set_threshold(4),
+            Err(MultisigError::InvalidThreshold { m: 4, n: 3 })
+        ));
+        assert_eq!(wallet.info().threshold, 2);
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        // Lowering the threshold makes the single signature sufficient
+        wallet.set_threshold(1).unwrap();
+        assert!(wallet.has_enough_signatures(&tx.id).unwrap());
+
+        // Raising it past the collected signatures blocks execution again
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        wallet.set_threshold(3).unwrap();
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+        assert!(matches!(
+            wallet.execute_transaction(&tx.id),
+            Err(MultisigError::InsufficientSignatures { required: 3, actual: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_set_threshold_keeps_executed_transactions() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.execute_transaction(&tx.id).unwrap();
+
+        wallet.set_threshold(2).unwrap();
+
+        let pending = wallet.pending_transactions.get(&tx.id).unwrap();
+        assert!(pending.executed);
+    }
+
+    #[test]
+    fn test_weighted_wallet() {
+        let (sk_founder, pk_founder) = generate_keypair().unwrap();
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new_weighted(
+            3,
+            vec![(pk_founder, 2), (pk1, 1), (pk2, 1)],
+        ).unwrap();
+        assert_eq!(wallet.info().total_weight, 4);
+
+        // The founder's key alone carries 2 of the 3 required votes
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk_founder, wallet.wallet_id()).unwrap(), &pk_founder).unwrap();
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert!(wallet.has_enough_signatures(&tx.id).unwrap());
+
+        // Two employees are not enough without the founder
+        let tx = Transaction::new("recipient".to_string(), 500, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        assert!(matches!(
+            wallet.execute_transaction(&tx.id),
+            Err(MultisigError::InsufficientSignatures { required: 3, actual: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_weights() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+
+        let zero_weight = MultisigWallet::new_weighted(1, vec![(pk1, 0), (pk2, 1)]);
+        assert!(matches!(zero_weight, Err(MultisigError::InvalidWeight)));
+
+        let unreachable = MultisigWallet::new_weighted(4, vec![(pk1, 2), (pk2, 1)]);
+        assert!(matches!(unreachable, Err(MultisigError::InvalidThreshold { m: 4, n: 3 })));
+    }
+
+    #[test]
+    fn test_expired_transaction() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+
+        let now = current_timestamp();
+        let expired = Transaction::new("recipient".to_string(), 1000, None).with_expiry(now - 60);
+        let live = Transaction::new("recipient".to_string(), 1000, None).with_expiry(now + 3600);
+        wallet.propose_transaction(expired.clone()).unwrap();
+        wallet.propose_transaction(live.clone()).unwrap();
+
+        let sig = expired.sign(&sk1, wallet.wallet_id()).unwrap();
+        assert!(matches!(
+            wallet.add_signature(&expired.id, sig, &pk1),
+            Err(MultisigError::TransactionExpired)
+        ));
+        assert!(matches!(
+            wallet.execute_transaction(&expired.id),
+            Err(MultisigError::TransactionExpired)
+        ));
+
+        wallet.add_signature(&live.id, live.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        assert_eq!(wallet.prune_expired(), 1);
+        assert_eq!(wallet.info().pending_count, 1);
+        assert!(wallet.execute_transaction(&live.id).is_ok());
+    }
+
+    #[test]
+    fn test_execute_after_expiry() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+
+        let clock = FixedClock::new(1_000_000);
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap().with_clock(clock.clone());
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None)
+            .with_timestamp(1_000_000)
+            .with_expiry(1_000_060);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        // The deadline passes after signatures were collected
+        clock.advance(120);
+
+        assert!(matches!(
+            wallet.execute_transaction(&tx.id),
+            Err(MultisigError::TransactionExpired)
+        ));
+    }
+
+    #[test]
+    fn test_cancel_transaction() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.cancel_transaction(&tx.id).unwrap();
+        assert_eq!(wallet.info().pending_count, 0);
+        assert!(matches!(
+            wallet.cancel_transaction(&tx.id),
+            Err(MultisigError::TransactionNotFound { .. })
+        ));
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.execute_transaction(&tx.id).unwrap();
+        assert!(matches!(
+            wallet.cancel_transaction(&tx.id),
+            Err(MultisigError::TransactionAlreadyExecuted)
+        ));
+    }
+
+    #[test]
+    fn test_reject_transaction() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let (_, pk_outsider) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        assert!(matches!(
+            wallet.reject_transaction(&tx.id, &pk_outsider),
+            Err(MultisigError::UnauthorizedSigner { .. })
+        ));
+
+        // One veto in a 2-of-3 still leaves the threshold reachable
+        wallet.reject_transaction(&tx.id, &pk2).unwrap();
+        assert!(matches!(
+            wallet.reject_transaction(&tx.id, &pk2),
+            Err(MultisigError::DuplicateRejection)
+        ));
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        // A second veto makes two signatures impossible
+        wallet.reject_transaction(&tx.id, &pk3).unwrap();
+        assert!(wallet.pending_transactions.get(&tx.id).unwrap().rejected);
+
+        let sig = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        assert!(matches!(
+            wallet.add_signature(&tx.id, sig, &pk1),
+            Err(MultisigError::TransactionRejected)
+        ));
+        assert!(wallet.execute_transaction(&tx.id).is_err());
+    }
+
+    #[test]
+    fn test_executable_reachable() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert!(wallet.is_executable_reachable(&tx.id).unwrap());
+
+        // Waiting on pk3, not dead
+        wallet.reject_transaction(&tx.id, &pk2).unwrap();
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+        assert!(wallet.is_executable_reachable(&tx.id).unwrap());
+
+        // With pk3 gone only pk1 can approve, which is not enough
+        wallet.remove_signer(&pk3).unwrap();
+        assert!(!wallet.is_executable_reachable(&tx.id).unwrap());
+
+        wallet.set_threshold(1).unwrap();
+        assert!(wallet.is_executable_reachable(&tx.id).unwrap());
+        wallet.execute_transaction(&tx.id).unwrap();
+        assert!(!wallet.is_executable_reachable(&tx.id).unwrap());
+
+        assert!(matches!(
+            wallet.is_executable_reachable("missing"),
+            Err(MultisigError::TransactionNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_save_and_load_wallet() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        let path = std::env::temp_dir().join(format!("multisig-wallet-{}.json", tx.id));
+        wallet.save_to_file(&path).unwrap();
+        let mut loaded = MultisigWallet::load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.authorized_keys, wallet.authorized_keys);
+        assert_eq!(loaded.get_signature_count(&tx.id).unwrap(), 1);
+
+        // The reloaded wallet still verifies and accepts signatures
+        loaded.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        assert!(loaded.execute_transaction(&tx.id).is_ok());
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let path = std::env::temp_dir().join("multisig-wallet-does-not-exist.json");
+        let result = MultisigWallet::load_from_file(&path);
+        assert!(matches!(result, Err(MultisigError::IoError(_))));
+    }
+
+    #[test]
+    fn test_serde_round_trip_restores_keys() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+
+        let wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        let json = serde_json::to_string(&wallet).unwrap();
+        let mut restored: MultisigWallet = serde_json::from_str(&json).unwrap();
+
+        assert!(restored.is_authorized(&pk1));
+        assert!(restored.is_authorized(&pk2));
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        restored.propose_transaction(tx.clone()).unwrap();
+        assert!(restored.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).is_ok());
+    }
+
+    #[test]
+    fn test_deserialize_rejects_invalid_key_hex() {
+        let (_, pk1) = generate_keypair().unwrap();
+
+        let wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+        let json = serde_json::to_string(&wallet)
+            .unwrap()
+            .replace(&hex::encode(pk1.serialize()), "02deadbeef");
+
+        let result: std::result::Result<MultisigWallet, _> = serde_json::from_str(&json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_query_pending_transactions() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        let waiting = Transaction::new("recipient".to_string(), 100, None);
+        let ready = Transaction::new("recipient".to_string(), 200, None);
+        let executed = Transaction::new("recipient".to_string(), 300, None);
+        for tx in [&waiting, &ready, &executed] {
+            wallet.propose_transaction(tx.clone()).unwrap();
+            wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        }
+        for tx in [&ready, &executed] {
+            wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        }
+        wallet.execute_transaction(&executed.id).unwrap();
+
+        let mut expected = vec![waiting.id.clone(), ready.id.clone()];
+        expected.sort();
+        assert_eq!(wallet.pending_ids(), expected);
+        assert_eq!(wallet.pending_transactions().len(), 2);
+        assert!(wallet.pending_transactions().iter().all(|tx| tx.id != executed.id));
+        assert_eq!(wallet.transactions_ready_to_execute(), vec![ready.id.clone()]);
+    }
+
+    #[test]
+    fn test_signers_for_and_pending_signers() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        assert!(wallet.signers_for(&tx.id).unwrap().is_empty());
+        assert_eq!(wallet.pending_signers(&tx.id).unwrap(), vec![pk1, pk2, pk3]);
+
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert_eq!(wallet.signers_for(&tx.id).unwrap(), vec![pk1]);
+        assert_eq!(wallet.pending_signers(&tx.id).unwrap(), vec![pk2, pk3]);
+
+        assert!(matches!(
+            wallet.signers_for("missing"),
+            Err(MultisigError::TransactionNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_add_signatures_batch() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (sk3, pk3) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        let added = wallet.add_signatures_batch(&tx.id, vec![
+            (tx.sign(&sk1, wallet.wallet_id()).unwrap(), pk1),
+            (tx.sign(&sk2, wallet.wallet_id()).unwrap(), pk2),
+        ]).unwrap();
+        assert_eq!(added, 2);
+        assert!(wallet.has_enough_signatures(&tx.id).unwrap());
+
+        // Failures are collected without discarding the valid signature
+        let (sk_outsider, pk_outsider) = generate_keypair().unwrap();
+        let result = wallet.add_signatures_batch(&tx.id, vec![
+            (tx.sign(&sk1, wallet.wallet_id()).unwrap(), pk1),
+            (tx.sign(&sk_outsider, wallet.wallet_id()).unwrap(), pk_outsider),
+            (tx.sign(&sk2, wallet.wallet_id()).unwrap(), pk3),
+            (tx.sign(&sk3, wallet.wallet_id()).unwrap(), pk3),
+        ]);
+
+        match result {
+            Err(MultisigError::BatchFailed { added, failures }) => {
+                assert_eq!(added, 1);
+                assert_eq!(failures.len(), 3);
+                assert!(matches!(failures[0], (0, MultisigError::DuplicateSignature)));
+                assert!(matches!(failures[1], (1, MultisigError::UnauthorizedSigner { .. })));
+                assert!(matches!(failures[2], (2, MultisigError::InvalidSignature { .. })));
+            }
+            other => panic!("expected BatchFailed, got {:?}", other),
+        }
+        assert_eq!(wallet.get_signature_count(&tx.id).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_signatures_remaining() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (sk3, pk3) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(3, vec![pk1, pk2, pk3]).unwrap();
+        let tx = Transaction::new("recipient", 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        assert_eq!(wallet.signatures_remaining(&tx.id).unwrap(), 3);
+
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        assert_eq!(wallet.signatures_remaining(&tx.id).unwrap(), 1);
+
+        // A removed signer's signature stops counting
+        wallet.set_threshold(2).unwrap();
+        assert_eq!(wallet.signatures_remaining(&tx.id).unwrap(), 0);
+        wallet.remove_signer(&pk2).unwrap();
+        assert_eq!(wallet.signatures_remaining(&tx.id).unwrap(), 1);
+        wallet.add_signature(&tx.id, tx.sign(&sk3, wallet.wallet_id()).unwrap(), &pk3).unwrap();
+        assert_eq!(wallet.signatures_remaining(&tx.id).unwrap(), 0);
+
+        // Weighted wallets report weight
+        let mut weighted = MultisigWallet::new_weighted(5, vec![(pk1, 3), (pk2, 1), (pk3, 1)]).unwrap();
+        let tx = Transaction::new("recipient", 1000, None);
+        weighted.propose_transaction(tx.clone()).unwrap();
+        weighted.add_signature(&tx.id, tx.sign(&sk1, weighted.wallet_id()).unwrap(), &pk1).unwrap();
+        assert_eq!(weighted.signatures_remaining(&tx.id).unwrap(), 2);
+
+        assert!(matches!(
+            weighted.signatures_remaining("missing"),
+            Err(MultisigError::TransactionNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_custom_id_strategy() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        #[derive(Default)]
+        struct Sequential(AtomicU64);
+
+        impl IdStrategy for Sequential {
+            fn compute_id(&self, _tx: &Transaction) -> String {
+                format!("tx-{}", self.0.fetch_add(1, Ordering::SeqCst) + 1)
+            }
+        }
+
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let ids = Sequential::default();
+        let first = Transaction::new_with_id_strategy("alice", 100, None, &ids);
+        let second = Transaction::new_with_id_strategy("bob", 200, None, &ids);
+        assert_eq!((first.id.as_str(), second.id.as_str()), ("tx-1", "tx-2"));
+
+        // The default wallet insists on content-hash ids
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+        assert!(matches!(
+            wallet.propose_transaction(first.clone()),
+            Err(MultisigError::TransactionIdMismatch)
+        ));
+
+        let mut wallet = wallet.with_id_strategy(Sequential::default());
+        wallet.propose_transaction(first.clone()).unwrap();
+        wallet.propose_transaction(second).unwrap();
+
+        // Uniqueness is still enforced
+        let mut clash = Transaction::new("carol", 300, None);
+        clash.id = first.id.clone();
+        assert!(matches!(
+            wallet.propose_transaction(clash),
+            Err(MultisigError::DuplicateTransaction)
+        ));
+
+        wallet.add_signature("tx-1", first.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert_eq!(wallet.execute_transaction("tx-1").unwrap().amount, 100);
+        assert!(matches!(
+            wallet.propose_transaction(first),
+            Err(MultisigError::TransactionAlreadyExecuted)
+        ));
+    }
+
+    #[test]
+    fn test_batch_after_execution() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        let tx = Transaction::new("recipient", 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        // Collected while the transaction was still pending
+        let late = vec![(tx.sign(&sk2, wallet.wallet_id()).unwrap(), pk2)];
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.execute_transaction(&tx.id).unwrap();
+
+        let events = wallet.events().len();
+        assert!(matches!(
+            wallet.add_signatures_batch(&tx.id, late),
+            Err(MultisigError::TransactionAlreadyExecuted)
+        ));
+        assert_eq!(wallet.get_signature_count(&tx.id).unwrap(), 1);
+        assert_eq!(wallet.events().len(), events);
+        assert_eq!(wallet.metrics().signatures_added, 1);
+    }
+
+    #[test]
+    fn test_schnorr_wallet() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let keypair1 = secp256k1::Keypair::from_secret_key(context(), &sk1);
+        let keypair2 = secp256k1::Keypair::from_secret_key(context(), &sk2);
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2])
+            .unwrap()
+            .with_signature_scheme(SignatureScheme::Schnorr);
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        // ECDSA signatures are refused by a Schnorr wallet
+        assert!(matches!(
+            wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1),
+            Err(MultisigError::SignatureSchemeMismatch)
+        ));
+
+        wallet.add_schnorr_signature(&tx.id, tx.sign_schnorr(&keypair1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert!(matches!(
+            wallet.add_schnorr_signature(&tx.id, tx.sign_schnorr(&keypair1, wallet.wallet_id()).unwrap(), &pk2),
+            Err(MultisigError::InvalidSignature { .. })
+        ));
+        wallet.add_schnorr_signature(&tx.id, tx.sign_schnorr(&keypair2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+
+        let pending = wallet.pending_transactions.get(&tx.id).unwrap();
+        assert!(pending.signatures.values().all(|sig| sig.starts_with("schnorr:")));
+        assert!(wallet.execute_transaction(&tx.id).is_ok());
+    }
+
+    #[test]
+    fn test_add_recoverable_signature() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (sk_outsider, _) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        let outsider_sig = tx.sign_recoverable(&sk_outsider, wallet.wallet_id()).unwrap();
+        assert!(matches!(
+            wallet.add_recoverable_signature(&tx.id, outsider_sig),
+            Err(MultisigError::UnauthorizedSigner { .. })
+        ));
+
+        let signer = wallet.add_recoverable_signature(&tx.id, tx.sign_recoverable(&sk1, wallet.wallet_id()).unwrap()).unwrap();
+        assert_eq!(signer, pk1);
+        assert_eq!(wallet.signers_for(&tx.id).unwrap(), vec![pk1]);
+    }
+
+    #[test]
+    fn test_from_hex_keys() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let keys_hex = vec![hex::encode(pk1.serialize()), hex::encode(pk2.serialize())];
+
+        let wallet = MultisigWallet::from_hex_keys(2, keys_hex.clone()).unwrap();
+        assert_eq!(wallet.authorized_keys, vec![pk1, pk2]);
+        assert_eq!(wallet.authorized_keys_hex, keys_hex);
+
+        let bad_keys = vec![keys_hex[0].clone(), "not-hex".to_string()];
+        let err = MultisigWallet::from_hex_keys(1, bad_keys).unwrap_err();
+        assert!(matches!(err, MultisigError::InvalidPublicKey { index: Some(1) }));
+        assert_eq!(err.to_string(), "Invalid public key at index 1");
+        assert_eq!(
+            MultisigError::InvalidPublicKey { index: None }.to_string(),
+            "Invalid public key"
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_signer_order() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+
+        let wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+        let reordered = MultisigWallet::new(2, vec![pk3, pk1, pk2]).unwrap();
+        assert_eq!(wallet.fingerprint(), reordered.fingerprint());
+        assert_eq!(wallet.fingerprint().len(), 16);
+
+        let mut grown = MultisigWallet::new(2, vec![pk2, pk1]).unwrap();
+        assert_ne!(grown.fingerprint(), wallet.fingerprint());
+        grown.add_signer(pk3).unwrap();
+        assert_eq!(grown.fingerprint(), wallet.fingerprint());
+
+        let other_threshold = MultisigWallet::new(3, vec![pk1, pk2, pk3]).unwrap();
+        assert_ne!(other_threshold.fingerprint(), wallet.fingerprint());
+    }
+
+    #[test]
+    fn test_duplicate_keys_rejected() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+
+        // Looks like 2-of-3, but one holder of pk1 could sign twice
+        assert!(matches!(
+            MultisigWallet::new(2, vec![pk1, pk2, pk1]),
+            Err(MultisigError::DuplicateSigner)
+        ));
+        assert!(matches!(
+            MultisigWallet::new_weighted(2, vec![(pk1, 1), (pk1, 1)]),
+            Err(MultisigError::DuplicateSigner)
+        ));
+
+        let keys_hex = vec![hex::encode(pk2.serialize()), hex::encode(pk2.serialize())];
+        assert!(matches!(
+            MultisigWallet::from_hex_keys(1, keys_hex),
+            Err(MultisigError::DuplicateSigner)
+        ));
+    }
+
+    #[test]
+    fn test_replay_protection() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        assert!(matches!(
+            wallet.propose_transaction(tx.clone()),
+            Err(MultisigError::DuplicateTransaction)
+        ));
+
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.execute_transaction(&tx.id).unwrap();
+
+        // Even once the pending entry is gone the id stays burned
+        wallet.pending_transactions.remove(&tx.id);
+        assert!(matches!(
+            wallet.propose_transaction(tx),
+            Err(MultisigError::TransactionAlreadyExecuted)
+        ));
+    }
+
+    #[test]
+    fn test_max_amount() {
+        let (_, pk1) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap().with_max_amount(1000);
+
+        let at_limit = Transaction::new("recipient".to_string(), 1000, None);
+        assert!(wallet.propose_transaction(at_limit).is_ok());
+
+        let over_limit = Transaction::new("recipient".to_string(), 1001, None);
+        assert!(matches!(
+            wallet.propose_transaction(over_limit),
+            Err(MultisigError::AmountExceedsLimit { amount: 1001, limit: 1000 })
+        ));
+        assert_eq!(wallet.info().pending_count, 1);
+    }
+
+    #[test]
+    fn test_zero_amount() {
+        let (_, pk1) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+        assert!(wallet.propose_transaction(Transaction::new("recipient".to_string(), 0, None)).is_ok());
+
+        let mut wallet = wallet.with_allow_zero_amount(false);
+        assert!(matches!(
+            wallet.propose_transaction(Transaction::new("recipient".to_string(), 0, None)),
+            Err(MultisigError::ZeroAmount)
+        ));
+    }
+
+    #[test]
+    fn test_recipient_format_enforced() {
+        let (_, pk1) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+        assert!(matches!(
+            wallet.propose_transaction(Transaction::new(String::new(), 1000, None)),
+            Err(MultisigError::InvalidRecipient)
+        ));
+
+        let mut wallet = wallet.with_recipient_format(RecipientFormat::HexPubkey);
+        assert!(matches!(
+            wallet.propose_transaction(Transaction::new("recipient".to_string(), 1000, None)),
+            Err(MultisigError::InvalidRecipient)
+        ));
+        let tx = Transaction::new(hex::encode(pk1.serialize()), 1000, None);
+        assert!(wallet.propose_transaction(tx).is_ok());
+    }
+
+    #[test]
+    fn test_audit_log() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+
+        let executed = Transaction::new("recipient".to_string(), 1000, None);
+        let cancelled = Transaction::new("recipient".to_string(), 2000, None);
+        wallet.propose_transaction(executed.clone()).unwrap();
+        wallet.add_signature(&executed.id, executed.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.execute_transaction(&executed.id).unwrap();
+        wallet.propose_transaction(cancelled.clone()).unwrap();
+        wallet.cancel_transaction(&cancelled.id).unwrap();
+
+        let events = wallet.events();
+        assert_eq!(events.len(), 5);
+        assert!(matches!(&events[0], WalletEvent::TransactionProposed { tx_id, .. } if *tx_id == executed.id));
+        assert!(matches!(
+            &events[1],
+            WalletEvent::SignatureAdded { signer, .. } if *signer == hex::encode(pk1.serialize())
+        ));
+        assert!(matches!(&events[2], WalletEvent::TransactionExecuted { .. }));
+        assert!(matches!(&events[3], WalletEvent::TransactionProposed { .. }));
+        assert!(matches!(&events[4], WalletEvent::TransactionCancelled { tx_id, .. } if *tx_id == cancelled.id));
+
+        // The log is persisted with the wallet
+        let json = serde_json::to_string(&wallet).unwrap();
+        let restored: MultisigWallet = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.events(), wallet.events());
+    }
+
+    #[test]
+    fn test_events_jsonl_roundtrip() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+        for amount in 1..=3 {
+            let tx = Transaction::new("recipient".to_string(), amount, None);
+            wallet.propose_transaction(tx.clone()).unwrap();
+            wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+            wallet.execute_transaction(&tx.id).unwrap();
+        }
+
+        let mut out = Vec::new();
+        wallet.export_events_jsonl(&mut out).unwrap();
+        let text = String::from_utf8(out.clone()).unwrap();
+        assert_eq!(text.lines().count(), 9);
+        assert!(text.lines().all(|line| serde_json::from_str::<serde_json::Value>(line).is_ok()));
+
+        let imported = MultisigWallet::import_events_jsonl(out.as_slice()).unwrap();
+        assert_eq!(imported, wallet.events());
+
+        let damaged = format!("{}\nnot json\n", text.lines().next().unwrap());
+        assert!(matches!(
+            MultisigWallet::import_events_jsonl(damaged.as_bytes()),
+            Err(MultisigError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_errors_carry_context() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        let err = wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk3).unwrap_err();
+        assert!(matches!(
+            &err,
+            MultisigError::UnauthorizedSigner { pubkey_hex } if *pubkey_hex == hex::encode(pk3.serialize())
+        ));
+
+        let err = wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk1).unwrap_err();
+        assert!(matches!(
+            &err,
+            MultisigError::InvalidSignature { tx_id, pubkey_hex }
+                if *tx_id == tx.id && *pubkey_hex == hex::encode(pk1.serialize())
+        ));
+
+        let err = wallet.get_signature_count("missing-tx").unwrap_err();
+        assert_eq!(err.to_string(), "Transaction missing-tx not found");
+    }
+
+    #[test]
+    fn test_default_expiry() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::builder()
+            .threshold(1)
+            .add_signer(pk1)
+            .default_expiry(3600)
+            .build()
+            .unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        let deadline = wallet.pending_transactions.get(&tx.id).unwrap().expires_at.unwrap();
+        assert!(deadline >= current_timestamp() + 3590);
+
+        // A transaction's own expiry takes precedence over the default
+        let own = Transaction::new("recipient".to_string(), 1000, None).with_expiry(1);
+        wallet.propose_transaction(own.clone()).unwrap();
+        assert!(wallet.pending_transactions.get(&own.id).unwrap().expires_at.is_none());
+        assert!(matches!(
+            wallet.add_signature(&own.id, own.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1),
+            Err(MultisigError::TransactionExpired)
+        ));
+    }
+
+    #[test]
+    fn test_cross_wallet_replay_rejected() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+
+        let mut wallet_a = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        let mut wallet_b = MultisigWallet::new(1, vec![pk1, pk3]).unwrap();
+        assert_ne!(wallet_a.wallet_id(), wallet_b.wallet_id());
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet_a.propose_transaction(tx.clone()).unwrap();
+        wallet_b.propose_transaction(tx.clone()).unwrap();
+
+        let sig_for_a = tx.sign(&sk1, wallet_a.wallet_id()).unwrap();
+        wallet_a.add_signature(&tx.id, sig_for_a, &pk1).unwrap();
+        assert!(matches!(
+            wallet_b.add_signature(&tx.id, sig_for_a, &pk1),
+            Err(MultisigError::InvalidSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn test_wallet_id_independent_of_key_order() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+
+        let wallet_a = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        let wallet_b = MultisigWallet::new(1, vec![pk2, pk1]).unwrap();
+        let wallet_c = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        assert_eq!(wallet_a.wallet_id(), wallet_b.wallet_id());
+        assert_ne!(wallet_a.wallet_id(), wallet_c.wallet_id());
+    }
+
+    #[test]
+    fn test_high_s_signatures() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+
+        let mut lenient = MultisigWallet::new(1, vec![pk1]).unwrap();
+        let mut strict = MultisigWallet::new(1, vec![pk1]).unwrap().with_require_low_s(true);
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        lenient.propose_transaction(tx.clone()).unwrap();
+        strict.propose_transaction(tx.clone()).unwrap();
+
+        let low = tx.sign(&sk1, strict.wallet_id()).unwrap();
+        let high = high_s_form(&low);
+
+        assert!(matches!(
+            strict.add_signature(&tx.id, high, &pk1),
+            Err(MultisigError::NonCanonicalSignature)
+        ));
+        strict.add_signature(&tx.id, low, &pk1).unwrap();
+
+        // Without the requirement the signature is stored normalized
+        lenient.add_signature(&tx.id, high, &pk1).unwrap();
+        let stored = &lenient.pending_transactions.get(&tx.id).unwrap().signatures;
+        assert_eq!(stored.values().next().unwrap(), &hex::encode(low.serialize_compact()));
+    }
+
+    #[test]
+    fn test_malleated_signature_is_duplicate() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        let low = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        let high = high_s_form(&low);
+        assert_ne!(low.serialize_compact(), high.serialize_compact());
+
+        wallet.add_signature(&tx.id, low, &pk1).unwrap();
+        assert!(matches!(
+            wallet.add_signature(&tx.id, high, &pk1),
+            Err(MultisigError::DuplicateSignature)
+        ));
+        assert!(matches!(
+            wallet.add_signatures_batch(&tx.id, vec![(high, pk1)]),
+            Err(MultisigError::BatchFailed { added: 0, .. })
+        ));
+        assert_eq!(wallet.get_signature_count(&tx.id).unwrap(), 1);
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+    }
+
+    #[test]
+    fn test_export_import_pending() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+
+        let mut alice = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+        let mut bob = alice.clone();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        alice.propose_transaction(tx.clone()).unwrap();
+        bob.propose_transaction(tx.clone()).unwrap();
+
+        let sig1 = tx.sign(&sk1, alice.wallet_id()).unwrap();
+        let sig2 = tx.sign(&sk2, bob.wallet_id()).unwrap();
+        alice.add_signature(&tx.id, sig1, &pk1).unwrap();
+        bob.add_signature(&tx.id, sig2, &pk2).unwrap();
+
+        let blob = alice.export_pending(&tx.id).unwrap();
+        assert_eq!(bob.import_pending(&blob).unwrap(), 1);
+        assert!(bob.has_enough_signatures(&tx.id).unwrap());
+
+        // Importing the same blob again adds nothing
+        assert_eq!(bob.import_pending(&blob).unwrap(), 0);
+
+        let mut stranger = MultisigWallet::new(1, vec![pk1]).unwrap();
+        assert!(matches!(
+            stranger.import_pending(&blob),
+            Err(MultisigError::TransactionNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_merge_signatures() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (outsider_sk, outsider_pk) = generate_keypair().unwrap();
+
+        let mut ours = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+        let mut theirs = MultisigWallet::new(2, vec![pk1, pk2, outsider_pk]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        ours.propose_transaction(tx.clone()).unwrap();
+        theirs.propose_transaction(tx.clone()).unwrap();
+
+        // Both wallets need the same id for signatures to carry over
+        theirs.wallet_id = ours.wallet_id.clone();
+
+        let sig1 = tx.sign(&sk1, ours.wallet_id()).unwrap();
+        ours.add_signature(&tx.id, sig1, &pk1).unwrap();
+        theirs.add_signature(&tx.id, sig1, &pk1).unwrap();
+        theirs.add_signature(&tx.id, tx.sign(&sk2, ours.wallet_id()).unwrap(), &pk2).unwrap();
+        theirs
+            .add_signature(&tx.id, tx.sign(&outsider_sk, ours.wallet_id()).unwrap(), &outsider_pk)
+            .unwrap();
+
+        // pk1 is already present and the outsider is not our signer
+        assert_eq!(ours.merge_signatures(&tx.id, &theirs).unwrap(), 1);
+        assert_eq!(ours.get_signature_count(&tx.id).unwrap(), 2);
+        assert_eq!(ours.merge_signatures(&tx.id, &theirs).unwrap(), 0);
+
+        assert!(matches!(
+            ours.merge_signatures("missing", &theirs),
+            Err(MultisigError::TransactionNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_timestamp_sanity() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1])
+            .unwrap()
+            .with_max_clock_skew(60)
+            .with_max_age(3_600);
+
+        let mut future = Transaction::new("recipient".to_string(), 1000, None);
+        future.timestamp = current_timestamp() + 600;
+        future.id = future.calculate_id();
+        assert!(matches!(
+            wallet.propose_transaction(future),
+            Err(MultisigError::TimestampOutOfRange { .. })
+        ));
+
+        let mut stale = Transaction::new("recipient".to_string(), 1000, None);
+        stale.timestamp = current_timestamp() - 7_200;
+        stale.id = stale.calculate_id();
+        assert!(matches!(
+            wallet.propose_transaction(stale),
+            Err(MultisigError::TimestampOutOfRange { .. })
+        ));
+
+        let mut slightly_ahead = Transaction::new("recipient".to_string(), 1000, None);
+        slightly_ahead.timestamp = current_timestamp() + 30;
+        slightly_ahead.id = slightly_ahead.calculate_id();
+        assert!(wallet.propose_transaction(slightly_ahead).is_ok());
+    }
+
+    #[test]
+    fn test_transaction_status() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        let status = wallet.transaction_status(&tx.id).unwrap();
+        assert_eq!(status.signatures_collected, 1);
+        assert_eq!(status.signatures_required, 2);
+        assert!(!status.is_ready);
+        assert_eq!(status.signers_done, vec![hex::encode(pk1.serialize())]);
+        assert_eq!(
+            status.signers_remaining,
+            vec![hex::encode(pk2.serialize()), hex::encode(pk3.serialize())]
+        );
+
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        assert!(wallet.transaction_status(&tx.id).unwrap().is_ready);
+
+        wallet.execute_transaction(&tx.id).unwrap();
+        let status = wallet.transaction_status(&tx.id).unwrap();
+        assert!(status.is_executed);
+        assert!(!status.is_ready);
+
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["signatures_required"], 2);
+    }
+
+    #[test]
+    fn test_hash_algorithm_wallet() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1])
+            .unwrap()
+            .with_hash_algorithm(HashAlgorithm::DoubleSha256);
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        // A SHA-256 signature does not match the wallet's algorithm
+        let sha256_sig = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        assert!(matches!(
+            wallet.add_signature(&tx.id, sha256_sig, &pk1),
+            Err(MultisigError::InvalidSignature { .. })
+        ));
+
+        let sig = tx.sign_with_hash(&sk1, wallet.wallet_id(), HashAlgorithm::DoubleSha256).unwrap();
+        wallet.add_signature(&tx.id, sig, &pk1).unwrap();
+        assert!(wallet.has_enough_signatures(&tx.id).unwrap());
+    }
+
+    #[test]
+    fn test_uncompressed_hex_keys() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let compressed = hex::encode(pk1.serialize());
+        let uncompressed = hex::encode(pk1.serialize_uncompressed());
+
+        let wallet = MultisigWallet::from_hex_keys(1, vec![uncompressed.clone()]).unwrap();
+        assert_eq!(wallet.authorized_keys_hex, vec![compressed.clone()]);
+        assert!(wallet.is_authorized_hex(&compressed));
+        assert!(wallet.is_authorized_hex(&uncompressed));
+        assert!(!wallet.is_authorized_hex(&hex::encode(pk2.serialize())));
+
+        let truncated = uncompressed[..128].to_string();
+        assert!(matches!(
+            MultisigWallet::from_hex_keys(1, vec![truncated]),
+            Err(MultisigError::InvalidPublicKey { index: Some(0) })
+        ));
+    }
+
+    #[test]
+    fn test_finalize_and_verify_bundle() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (sk3, pk3) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert!(matches!(
+            wallet.finalize(&tx.id),
+            Err(MultisigError::InsufficientSignatures { required: 2, actual: 1 })
+        ));
+
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk3, wallet.wallet_id()).unwrap(), &pk3).unwrap();
+
+        let bundle = wallet.finalize(&tx.id).unwrap();
+        assert_eq!(bundle.signatures.len(), 2);
+        assert!(wallet.verify_signed_transaction(&bundle).unwrap());
+
+        let mut repeated = bundle.clone();
+        repeated.signatures[1] = repeated.signatures[0].clone();
+        assert!(!wallet.verify_signed_transaction(&repeated).unwrap());
+
+        let mut tampered = bundle;
+        tampered.transaction.amount = 5000;
+        assert!(!wallet.verify_signed_transaction(&tampered).unwrap());
+    }
+
+    #[test]
+    fn test_pending_sorted_by_timestamp() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+        assert!(wallet.oldest_pending().is_none());
+
+        let now = current_timestamp();
+        let mut newest = Transaction::new("recipient".to_string(), 1, None);
+        newest.timestamp = now;
+        let mut tied_high = Transaction::new("recipient".to_string(), 2, None);
+        tied_high.timestamp = now - 100;
+        tied_high.nonce = 9;
+        let mut tied_low = Transaction::new("recipient".to_string(), 3, None);
+        tied_low.timestamp = now - 100;
+        tied_low.nonce = 1;
+
+        for tx in [&mut newest, &mut tied_high, &mut tied_low] {
+            tx.id = tx.calculate_id();
+            wallet.propose_transaction(tx.clone()).unwrap();
+        }
+
+        let order: Vec<&str> = wallet
+            .pending_sorted_by_timestamp()
+            .iter()
+            .map(|tx| tx.id.as_str())
+            .collect();
+        assert_eq!(order, vec![tied_low.id.as_str(), tied_high.id.as_str(), newest.id.as_str()]);
+        assert_eq!(wallet.oldest_pending().unwrap().id, tied_low.id);
+    }
+
+    #[test]
+    fn test_update_signature() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        let first = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx.id, first, &pk1).unwrap();
+
+        let resigned = tx.sign_recoverable(&sk1, wallet.wallet_id()).unwrap().to_standard();
+        assert!(matches!(
+            wallet.add_signature(&tx.id, resigned, &pk1),
+            Err(MultisigError::DuplicateSignature)
+        ));
+
+        wallet.update_signature(&tx.id, resigned, &pk1).unwrap();
+        assert_eq!(wallet.get_signature_count(&tx.id).unwrap(), 1);
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+
+        let forged = Transaction::new("elsewhere".to_string(), 1, None)
+            .sign(&sk1, wallet.wallet_id())
+            .unwrap();
+        assert!(matches!(
+            wallet.update_signature(&tx.id, forged, &pk1),
+            Err(MultisigError::InvalidSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tags_filter_and_bind_signatures() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+
+        let payroll = Transaction::new("alice".to_string(), 1000, None)
+            .with_tags(vec!["payroll".to_string()]);
+        let vendor = Transaction::new("acme".to_string(), 500, None)
+            .with_tags(vec!["vendor".to_string()]);
+        wallet.propose_transaction(payroll.clone()).unwrap();
+        wallet.propose_transaction(vendor).unwrap();
+
+        let tagged = wallet.pending_with_tag("payroll");
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].id, payroll.id);
+        assert!(wallet.pending_with_tag("refund").is_empty());
+
+        // A signature over the payroll tags does not cover a retagged copy
+        let signature = payroll.sign(&sk1, wallet.wallet_id()).unwrap();
+        let retagged = payroll.clone().with_tags(vec!["refund".to_string()]);
+        assert_ne!(retagged.signing_hash(), payroll.signing_hash());
+        wallet.pending_transactions.get_mut(&payroll.id).unwrap().transaction.tags =
+            vec!["refund".to_string()];
+        assert!(matches!(
+            wallet.add_signature(&payroll.id, signature, &pk1),
+            Err(MultisigError::TransactionIdMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        let json = wallet.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["threshold"], 2);
+        assert_eq!(value["authorized_keys_hex"][0], hex::encode(pk1.serialize()));
+        assert_eq!(value["pending_transactions"][&tx.id]["executed"], false);
+
+        let mut restored = MultisigWallet::from_json(&json).unwrap();
+        assert_eq!(restored.wallet_id(), wallet.wallet_id());
+        assert_eq!(restored.authorized_keys, wallet.authorized_keys);
+        assert_eq!(
+            restored.transaction_status(&tx.id).unwrap(),
+            wallet.transaction_status(&tx.id).unwrap()
+        );
+
+        let sig2 = tx.sign(&sk2, wallet.wallet_id()).unwrap();
+        restored.add_signature(&tx.id, sig2, &pk2).unwrap();
+        wallet.add_signature(&tx.id, sig2, &pk2).unwrap();
+        assert_eq!(restored.execute_transaction(&tx.id).unwrap().id, tx.id);
+        assert_eq!(wallet.execute_transaction(&tx.id).unwrap().id, tx.id);
+
+        assert!(matches!(
+            MultisigWallet::from_json("{\"threshold\": 1}"),
+            Err(MultisigError::SerializationError(_))
+        ));
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_bincode_roundtrip() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new_with_fee("recipient".to_string(), u128::MAX - 5, 5, None)
+            .with_tags(vec!["ops".to_string()]);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        let bytes = wallet.to_bincode().unwrap();
+        assert!(bytes.len() < wallet.to_json().unwrap().len());
+
+        let mut restored = MultisigWallet::from_bincode(&bytes).unwrap();
+        assert_eq!(restored.wallet_id(), wallet.wallet_id());
+        assert_eq!(restored.authorized_keys, wallet.authorized_keys);
+        assert_eq!(
+            restored.transaction_status(&tx.id).unwrap(),
+            wallet.transaction_status(&tx.id).unwrap()
+        );
+        assert_eq!(restored.execute_transaction(&tx.id).unwrap().amount, tx.amount);
+
+        assert!(matches!(
+            MultisigWallet::from_bincode(&bytes[..bytes.len() / 2]),
+            Err(MultisigError::BincodeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_can_execute() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+        assert_eq!(wallet.can_execute("missing").unwrap(), ExecutionReadiness::NotFound);
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert_eq!(
+            wallet.can_execute(&tx.id).unwrap(),
+            ExecutionReadiness::Insufficient { have: 1, need: 2 }
+        );
+
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        assert_eq!(wallet.can_execute(&tx.id).unwrap(), ExecutionReadiness::Ready);
+
+        wallet.execute_transaction(&tx.id).unwrap();
+        assert_eq!(wallet.can_execute(&tx.id).unwrap(), ExecutionReadiness::AlreadyExecuted);
+
+        let expiring = Transaction::new("recipient".to_string(), 1000, None).with_expiry(1);
+        wallet.propose_transaction(expiring.clone()).unwrap();
+        let pending = wallet.pending_transactions.get_mut(&expiring.id).unwrap();
+        for (pk, sk) in [(pk1, sk1), (pk2, sk2)] {
+            let sig = expiring.sign(&sk, &wallet.wallet_id).unwrap();
+            pending.signatures.insert(hex::encode(pk.serialize()), hex::encode(sig.serialize_compact()));
+        }
+        assert_eq!(wallet.can_execute(&expiring.id).unwrap(), ExecutionReadiness::Expired);
+    }
+
+    #[test]
+    fn test_required_signer_policy() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (sk3, pk3) = generate_keypair().unwrap();
+        let policy = Policy::Required { mandatory: vec![pk1], threshold: 2 };
+        let mut wallet = MultisigWallet::new_with_policy(policy.clone(), vec![pk1, pk2, pk3]).unwrap();
+        assert_eq!(wallet.policy(), policy);
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk3, wallet.wallet_id()).unwrap(), &pk3).unwrap();
+
+        // Two signatures meet the count, but the mandatory signer is missing
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+        assert!(matches!(
+            wallet.execute_transaction(&tx.id),
+            Err(MultisigError::MissingRequiredSigners { .. })
+        ));
+
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert!(wallet.has_enough_signatures(&tx.id).unwrap());
+
+        let bundle = wallet.finalize(&tx.id).unwrap();
+        assert!(bundle.signatures.iter().any(|(pk_hex, _)| *pk_hex == hex::encode(pk1.serialize())));
+        assert!(wallet.verify_signed_transaction(&bundle).unwrap());
+
+        let (_, outsider) = generate_keypair().unwrap();
+        let bad_policy = Policy::Required { mandatory: vec![outsider], threshold: 1 };
+        assert!(matches!(
+            MultisigWallet::new_with_policy(bad_policy, vec![pk1]),
+            Err(MultisigError::UnauthorizedSigner { .. })
+        ));
+    }
+
+    #[test]
+    fn test_time_locked_execution() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+        let now = current_timestamp();
+
+        // Signatures can be collected while the lock is closed
+        let scheduled = Transaction::new("recipient".to_string(), 1000, None)
+            .with_not_before(now + 3_600);
+        wallet.propose_transaction(scheduled.clone()).unwrap();
+        let sig = scheduled.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&scheduled.id, sig, &pk1).unwrap();
+        assert!(wallet.transactions_ready_to_execute().is_empty());
+        assert!(matches!(
+            wallet.execute_transaction(&scheduled.id),
+            Err(MultisigError::TransactionNotYetValid { valid_at }) if valid_at == now + 3_600
+        ));
+
+        let opening_now = Transaction::new("recipient".to_string(), 1000, None).with_not_before(now);
+        wallet.propose_transaction(opening_now.clone()).unwrap();
+        let sig = opening_now.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&opening_now.id, sig, &pk1).unwrap();
+        assert!(wallet.execute_transaction(&opening_now.id).is_ok());
+    }
+
+    #[test]
+    fn test_on_ready_fires_once() {
+        use std::sync::Mutex;
+
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (sk3, pk3) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let fired = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&fired);
+        wallet.set_on_ready(Box::new(move |tx| sink.lock().unwrap().push(tx.id.clone())));
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert!(fired.lock().unwrap().is_empty());
+
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk3, wallet.wallet_id()).unwrap(), &pk3).unwrap();
+        assert_eq!(*fired.lock().unwrap(), vec![tx.id.clone()]);
+    }
+
+    #[test]
+    fn test_nonce_management() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+        assert_eq!(wallet.next_nonce("alice"), 0);
+
+        let first = wallet.propose_with_next_nonce("alice".to_string(), 100, None).unwrap();
+        let second = wallet.propose_with_next_nonce("alice".to_string(), 100, None).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(wallet.next_nonce("alice"), 2);
+        assert_eq!(wallet.next_nonce("bob"), 0);
+
+        // Same recipient and nonce, different contents
+        let reused = Transaction::with_nonce("alice".to_string(), 999, None, 1);
+        assert!(matches!(
+            wallet.propose_transaction(reused),
+            Err(MultisigError::DuplicateNonce { nonce: 1, .. })
+        ));
+
+        let other_recipient = Transaction::with_nonce("bob".to_string(), 999, None, 1);
+        assert!(wallet.propose_transaction(other_recipient).is_ok());
+    }
+
+    #[test]
+    fn test_stale_signatures_do_not_count() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        let sig1 = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx.id, sig1, &pk1).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        assert!(wallet.has_enough_signatures(&tx.id).unwrap());
+
+        wallet.remove_signer(&pk1).unwrap();
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+
+        // Even if a removed signer's entry slips back in, it is not counted
+        wallet.pending_transactions.get_mut(&tx.id).unwrap().signatures.insert(
+            hex::encode(pk1.serialize()),
+            hex::encode(sig1.serialize_compact()),
+        );
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+        assert_eq!(
+            wallet.can_execute(&tx.id).unwrap(),
+            ExecutionReadiness::Insufficient { have: 1, need: 2 }
+        );
+    }
+
+    #[test]
+    fn test_pending_totals() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        assert_eq!(wallet.pending_total_amount().unwrap(), 0);
+
+        let large = Transaction::new("recipient".to_string(), u128::MAX, None);
+        let signed = Transaction::new("recipient".to_string(), u128::MAX, Some("b".to_string()));
+        let executed = Transaction::new("recipient".to_string(), 7, None);
+        for tx in [&large, &signed, &executed] {
+            wallet.propose_transaction(tx.clone()).unwrap();
+        }
+        for tx in [&signed, &executed] {
+            wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        }
+        wallet.execute_transaction(&executed.id).unwrap();
+
+        assert_eq!(wallet.pending_total_ready().unwrap(), u128::MAX);
+        assert!(matches!(wallet.pending_total_amount(), Err(MultisigError::AmountOverflow)));
+
+        wallet.cancel_transaction(&large.id).unwrap();
+        assert_eq!(wallet.pending_total_amount().unwrap(), u128::MAX);
+    }
+
+    #[test]
+    fn test_aggregated_pubkey() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+
+        let wallet_a = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        let wallet_b = MultisigWallet::new(2, vec![pk2, pk1]).unwrap();
+        assert_eq!(wallet_a.aggregated_pubkey().unwrap(), pk1.combine(&pk2).unwrap());
+        assert_eq!(wallet_a.wallet_address().unwrap(), wallet_b.wallet_address().unwrap());
+
+        // A key and its negation sum to the point at infinity
+        let cancelling = MultisigWallet::new(1, vec![pk1, pk1.negate(context())]).unwrap();
+        assert!(matches!(
+            cancelling.aggregated_pubkey(),
+            Err(MultisigError::KeyAggregationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_collected_signatures() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        assert!(wallet.collected_signatures(&tx.id).unwrap().is_empty());
+
+        let sig1 = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx.id, sig1, &pk1).unwrap();
+
+        let collected = wallet.collected_signatures(&tx.id).unwrap();
+        assert_eq!(collected, vec![(pk1, sig1)]);
+        let payload = tx.signing_payload(wallet.wallet_id());
+        assert!(verify_signature(&payload, &collected[0].1, &collected[0].0).unwrap());
+
+        wallet.pending_transactions.get_mut(&tx.id).unwrap().signatures.insert(
+            hex::encode(pk2.serialize()),
+            "not-hex".to_string(),
+        );
+        assert!(matches!(
+            wallet.collected_signatures(&tx.id),
+            Err(MultisigError::CryptoError(_))
+        ));
+    }
+
+    #[test]
+    fn test_eip191_wallet() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2])
+            .unwrap()
+            .with_message_mode(MessageMode::Eip191);
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        // Signatures over the unframed payload are rejected
+        let wallet_id = wallet.wallet_id().to_string();
+        let standard = tx.sign_with_hash(&sk1, &wallet_id, HashAlgorithm::Keccak256).unwrap();
+        assert!(matches!(
+            wallet.add_signature(&tx.id, standard, &pk1),
+            Err(MultisigError::InvalidSignature { .. })
+        ));
+
+        wallet.add_signature(&tx.id, tx.sign_eip191(&sk1, &wallet_id).unwrap(), &pk1).unwrap();
+        wallet.add_signature(&tx.id, tx.sign_eip191(&sk2, &wallet_id).unwrap(), &pk2).unwrap();
+
+        let bundle = wallet.finalize(&tx.id).unwrap();
+        assert!(wallet.verify_signed_transaction(&bundle).unwrap());
+
+        let restored = MultisigWallet::from_json(&wallet.to_json().unwrap()).unwrap();
+        assert!(restored.verify_signed_transaction(&bundle).unwrap());
+    }
+
+    #[test]
+    fn test_propose_transactions_all_or_nothing() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap().with_max_amount(1000);
+
+        let first = Transaction::with_nonce("alice".to_string(), 100, None, 0);
+        let second = Transaction::with_nonce("bob".to_string(), 200, None, 0);
+        let too_large = Transaction::with_nonce("carol".to_string(), 5000, None, 0);
+
+        assert!(matches!(
+            wallet.propose_transactions(vec![first.clone(), second.clone(), too_large]),
+            Err(MultisigError::AmountExceedsLimit { .. })
+        ));
+        assert!(wallet.pending_ids().is_empty());
+        assert!(wallet.events().is_empty());
+
+        // Conflicts inside the batch are caught too
+        let reused_nonce = Transaction::with_nonce("alice".to_string(), 300, None, 0);
+        assert!(matches!(
+            wallet.propose_transactions(vec![first.clone(), reused_nonce]),
+            Err(MultisigError::DuplicateNonce { nonce: 0, .. })
+        ));
+        assert!(matches!(
+            wallet.propose_transactions(vec![first.clone(), first.clone()]),
+            Err(MultisigError::DuplicateTransaction)
+        ));
+        assert!(wallet.pending_ids().is_empty());
+
+        let ids = wallet.propose_transactions(vec![first.clone(), second.clone()]).unwrap();
+        assert_eq!(ids, vec![first.id.clone(), second.id.clone()]);
+        assert_eq!(wallet.pending_transactions().len(), 2);
+        assert_eq!(wallet.next_nonce("alice"), 1);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+        assert_eq!(wallet.metrics(), WalletMetrics::default());
+
+        let executed = Transaction::new("recipient".to_string(), 100, None);
+        let vetoed = Transaction::new("recipient".to_string(), 200, None);
+        wallet.propose_transactions(vec![executed.clone(), vetoed.clone()]).unwrap();
+        let sig1 = executed.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&executed.id, sig1, &pk1).unwrap();
+        let sig2 = executed.sign(&sk2, wallet.wallet_id()).unwrap();
+        wallet.add_signatures_batch(&executed.id, vec![(sig2, pk2)]).unwrap();
+        wallet.execute_transaction(&executed.id).unwrap();
+        wallet.reject_transaction(&vetoed.id, &pk3).unwrap();
+
+        let expected = WalletMetrics {
+            proposals: 2,
+            signatures_added: 2,
+            executions: 1,
+            rejections: 1,
+        };
+        assert_eq!(wallet.metrics(), expected);
+
+        let restored = MultisigWallet::from_json(&wallet.to_json().unwrap()).unwrap();
+        assert_eq!(restored.metrics(), expected);
+    }
+
+    #[test]
+    fn test_tampered_id_rejected() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+
+        let mut tampered = Transaction::new("recipient".to_string(), 1000, None);
+        assert!(tampered.verify_id());
+        tampered.amount = 1_000_000;
+        assert!(!tampered.verify_id());
+        assert!(matches!(
+            wallet.propose_transaction(tampered.clone()),
+            Err(MultisigError::TransactionIdMismatch)
+        ));
+
+        tampered.id = "forged".to_string();
+        assert!(matches!(
+            wallet.propose_transaction(tampered),
+            Err(MultisigError::TransactionIdMismatch)
+        ));
+        assert!(wallet.pending_ids().is_empty());
+    }
+
+    #[test]
+    fn test_pending_totals_include_fees() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+
+        let with_fee = Transaction::new_with_fee("recipient".to_string(), 1000, 15, None);
+        let without_fee = Transaction::new("recipient".to_string(), 500, None);
+        wallet.propose_transactions(vec![with_fee, without_fee]).unwrap();
+        assert_eq!(wallet.pending_total_amount().unwrap(), 1515);
+
+        let overflowing = Transaction::new_with_fee("recipient".to_string(), u128::MAX, 1, None);
+        assert!(matches!(
+            wallet.propose_transaction(overflowing),
+            Err(MultisigError::AmountOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_execute_all_ready() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let clock = FixedClock::new(10_000);
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap().with_clock(clock.clone());
+
+        let first = Transaction::new("a", 100, None).with_timestamp(9_000);
+        let second = Transaction::new("b", 200, None).with_timestamp(9_100);
+        let expiring = Transaction::new("c", 300, None).with_timestamp(9_200).with_expiry(10_050);
+        let locked = Transaction::new("d", 400, None).with_timestamp(9_300).with_not_before(20_000);
+        let short = Transaction::new("e", 500, None).with_timestamp(9_400);
+
+        for tx in [&first, &second, &expiring, &locked] {
+            wallet.propose_transaction(tx.clone()).unwrap();
+            for (sk, pk) in [(&sk1, &pk1), (&sk2, &pk2)] {
+                wallet.add_signature(&tx.id, tx.sign(sk, wallet.wallet_id()).unwrap(), pk).unwrap();
+            }
+        }
+        wallet.propose_transaction(short.clone()).unwrap();
+        wallet.add_signature(&short.id, short.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        clock.advance(100);
+        let outcomes = wallet.execute_all_ready();
+        let ids: Vec<&str> = outcomes.iter().map(|(tx_id, _)| tx_id.as_str()).collect();
+        assert_eq!(ids, [&first.id, &second.id, &expiring.id, &locked.id]);
+        assert_eq!(outcomes[0].1.as_ref().unwrap().amount, 100);
+        assert_eq!(outcomes[1].1.as_ref().unwrap().amount, 200);
+        assert!(matches!(outcomes[2].1, Err(MultisigError::TransactionExpired)));
+        assert!(matches!(
+            outcomes[3].1,
+            Err(MultisigError::TransactionNotYetValid { valid_at: 20_000 })
+        ));
+
+        // The one below threshold was left alone
+        assert_eq!(wallet.pending_ids().len(), 3);
+        assert!(wallet.pending_ids().contains(&short.id));
+        assert!(wallet.execute_all_ready().iter().all(|(_, outcome)| outcome.is_err()));
+    }
+
+    #[test]
+    fn test_signer_activity() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(3, vec![pk1, pk2, pk3]).unwrap();
+        let [hex1, hex2, hex3] = [pk1, pk2, pk3].map(|pk| hex::encode(pk.serialize()));
+
+        for amount in [100, 200] {
+            let tx = Transaction::new("recipient", amount, None);
+            wallet.propose_transaction(tx.clone()).unwrap();
+            wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+            if amount == 100 {
+                wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+            }
+        }
+
+        let activity = wallet.signer_activity();
+        assert_eq!(activity.len(), 3);
+        assert_eq!(activity[&hex1], 2);
+        assert_eq!(activity[&hex2], 1);
+        assert_eq!(activity[&hex3], 0);
+        assert_eq!(wallet.least_active_signers(2), vec![hex3, hex2]);
+        assert_eq!(wallet.least_active_signers(10).len(), 3);
+    }
+
+    #[test]
+    fn test_propose_and_sign() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (outsider_sk, outsider) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient", 1000, None);
+        assert!(matches!(
+            wallet.propose_and_sign(tx.clone(), &outsider_sk, &outsider),
+            Err(MultisigError::UnauthorizedSigner { .. })
+        ));
+        assert!(matches!(
+            wallet.propose_and_sign(tx.clone(), &sk2, &pk1),
+            Err(MultisigError::InvalidPrivateKey)
+        ));
+        assert!(wallet.pending_ids().is_empty());
+
+        let tx_id = wallet.propose_and_sign(tx.clone(), &sk1, &pk1).unwrap();
+        assert_eq!(tx_id, tx.id);
+        assert_eq!(wallet.get_signature_count(&tx_id).unwrap(), 1);
+        assert_eq!(wallet.signers_for(&tx_id).unwrap(), vec![pk1]);
+
+        wallet.add_signature(&tx_id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        assert!(wallet.execute_transaction(&tx_id).is_ok());
+
+        // Schnorr wallets get a Schnorr signature
+        let mut schnorr_wallet = MultisigWallet::new(1, vec![pk1])
+            .unwrap()
+            .with_signature_scheme(SignatureScheme::Schnorr);
+        let tx = Transaction::new("recipient", 1000, None);
+        let tx_id = schnorr_wallet.propose_and_sign(tx, &sk1, &pk1).unwrap();
+        assert!(schnorr_wallet.execute_transaction(&tx_id).is_ok());
+    }
+
+    #[test]
+    fn test_fee_priority_queue() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+        let now = current_timestamp();
+
+        let old_low = Transaction::new_with_fee("a", 100, 5, None).with_timestamp(now - 30);
+        let new_low = Transaction::new_with_fee("b", 100, 5, None).with_timestamp(now - 10);
+        let high = Transaction::new_with_fee("c", 100, 50, None).with_timestamp(now);
+        let unsigned = Transaction::new_with_fee("d".to_string(), 100, 500, None);
+        for tx in [&new_low, &high, &old_low] {
+            wallet.propose_transaction(tx.clone()).unwrap();
+            wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        }
+        wallet.propose_transaction(unsigned).unwrap();
+
+        assert_eq!(
+            wallet.ready_by_priority(),
+            vec![high.id.clone(), old_low.id.clone(), new_low.id.clone()]
+        );
+        assert_eq!(wallet.execute_next().unwrap().unwrap().id, high.id);
+        assert_eq!(wallet.execute_next().unwrap().unwrap().id, old_low.id);
+        assert_eq!(wallet.execute_next().unwrap().unwrap().id, new_low.id);
+        assert!(wallet.execute_next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_execution_receipt() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        assert!(wallet.receipt(&tx.id).is_none());
+
+        let receipt = wallet.execute_with_receipt(&tx.id).unwrap();
+        let mut signers = vec![hex::encode(pk1.serialize()), hex::encode(pk2.serialize())];
+        signers.sort();
+        assert_eq!(receipt.tx_id, tx.id);
+        assert_eq!(receipt.signers, signers);
+        assert_eq!(receipt.signature_count, 2);
+        assert!(receipt.executed_at >= tx.timestamp);
+        assert_eq!(wallet.receipt(&tx.id), Some(&receipt));
+
+        let restored = MultisigWallet::from_json(&wallet.to_json().unwrap()).unwrap();
+        assert_eq!(restored.receipt(&tx.id), Some(&receipt));
+    }
 
     #[test]
-    fn test_wallet_creation() {
+    fn test_mutated_body_rejects_signatures() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        // Swap in a different body, with a matching id, behind the wallet's back
+        let mutated = Transaction { amount: 1_000_000, ..tx.clone() };
+        let mutated = Transaction { id: mutated.calculate_id(), ..mutated };
+        wallet.pending_transactions.get_mut(&tx.id).unwrap().transaction = mutated.clone();
+
+        let sig2 = mutated.sign(&sk2, wallet.wallet_id()).unwrap();
+        assert!(matches!(
+            wallet.add_signature(&tx.id, sig2, &pk2),
+            Err(MultisigError::TransactionIdMismatch)
+        ));
+        assert!(matches!(
+            wallet.add_signatures_batch(&tx.id, vec![(sig2, pk2)]),
+            Err(MultisigError::TransactionIdMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_fixed_clock_drives_time_checks() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let clock = FixedClock::new(1_000_000);
+        let mut wallet = MultisigWallet::new(1, vec![pk1])
+            .unwrap()
+            .with_default_expiry(100)
+            .with_clock(clock.clone());
+        assert_eq!(wallet.now(), 1_000_000);
+
+        let locked = Transaction::new("recipient".to_string(), 1000, None)
+            .with_timestamp(1_000_000)
+            .with_not_before(1_000_050);
+        wallet.propose_transaction(locked.clone()).unwrap();
+        let signature = locked.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&locked.id, signature, &pk1).unwrap();
+        assert_eq!(
+            wallet.can_execute(&locked.id).unwrap(),
+            ExecutionReadiness::NotYetValid { valid_at: 1_000_050 }
+        );
+
+        clock.advance(60);
+        assert_eq!(wallet.can_execute(&locked.id).unwrap(), ExecutionReadiness::Ready);
+
+        clock.advance(60);
+        assert_eq!(wallet.can_execute(&locked.id).unwrap(), ExecutionReadiness::Expired);
+
+        // Transactions stamped with the real time are far in the clock's future
+        let real_time = Transaction::new("recipient".to_string(), 1000, None);
+        assert!(matches!(
+            wallet.propose_transaction(real_time),
+            Err(MultisigError::TimestampOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verification_cache() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert!(wallet.reverify_all(&tx.id).unwrap());
+
+        // Corrupt the stored signature behind the cache's back: the cached
+        // result is trusted, an explicit re-check is not fooled
+        let other = Transaction::new("recipient".to_string(), 1, None);
+        let wrong = hex::encode(other.sign(&sk1, wallet.wallet_id()).unwrap().serialize_compact());
+        let pk_hex = hex::encode(pk1.serialize());
+        let pending = wallet.pending_transactions.get_mut(&tx.id).unwrap();
+        pending.signatures.insert(pk_hex.clone(), wrong.clone());
+        let bundle = SignedTransaction {
+            transaction: tx.clone(),
+            signatures: vec![(pk_hex, wrong)],
+        };
+        assert!(wallet.verify_signed_transaction(&bundle).unwrap());
+        assert!(!wallet.reverify_all(&tx.id).unwrap());
+
+        // A reloaded wallet starts with an empty cache
+        let restored = MultisigWallet::from_json(&wallet.to_json().unwrap()).unwrap();
+        assert!(!restored.verify_signed_transaction(&bundle).unwrap());
+
+        // Changing the body invalidates cached results
+        wallet.pending_transactions.get_mut(&tx.id).unwrap().transaction.amount = 5;
+        assert!(!wallet.verify_signed_transaction(&bundle).unwrap());
+    }
+
+    #[test]
+    fn test_delegate_signs_for_principal() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let (delegate_sk, delegate) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+        assert!(matches!(
+            wallet.add_delegation(&delegate, pk1),
+            Err(MultisigError::UnauthorizedSigner { .. })
+        ));
+        assert!(matches!(wallet.add_delegation(&pk1, pk2), Err(MultisigError::DuplicateSigner)));
+        wallet.add_delegation(&pk1, delegate).unwrap();
+        assert!(matches!(wallet.add_signer(delegate), Err(MultisigError::DuplicateSigner)));
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        let tx_id = tx.id.clone();
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        // Delegate and principal share one slot
+        let delegate_sig = tx.sign(&delegate_sk, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx_id, delegate_sig, &delegate).unwrap();
+        let sig1 = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+        assert!(matches!(
+            wallet.add_signature(&tx_id, sig1, &pk1),
+            Err(MultisigError::DuplicateSignature)
+        ));
+        assert_eq!(wallet.signatures_remaining(&tx_id).unwrap(), 1);
+
+        let sig2 = tx.sign(&sk2, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx_id, sig2, &pk2).unwrap();
+        assert!(wallet.has_enough_signatures(&tx_id).unwrap());
+        let bundle = wallet.finalize(&tx_id).unwrap();
+        assert!(wallet.verify_signed_transaction(&bundle).unwrap());
+
+        // Revoking the delegation takes the proxy signature with it
+        wallet.remove_delegation(&pk1).unwrap();
+        assert!(!wallet.has_enough_signatures(&tx_id).unwrap());
+        assert!(!wallet.verify_signed_transaction(&bundle).unwrap());
+        let delegate_sig = tx.sign(&delegate_sk, wallet.wallet_id()).unwrap();
+        assert!(matches!(
+            wallet.add_signature(&tx_id, delegate_sig, &delegate),
+            Err(MultisigError::UnauthorizedSigner { .. })
+        ));
+        wallet.add_signature(&tx_id, sig1, &pk1).unwrap();
+        assert!(wallet.execute_transaction(&tx_id).is_ok());
+    }
+
+    #[test]
+    fn test_canonical_key_list_ignores_order() {
         let (_, pk1) = generate_keypair().unwrap();
         let (_, pk2) = generate_keypair().unwrap();
         let (_, pk3) = generate_keypair().unwrap();
-        
-        let wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]);
-        assert!(wallet.is_ok());
-        
-        let wallet = wallet.unwrap();
-        assert_eq!(wallet.threshold, 2);
-        assert_eq!(wallet.total_signers, 3);
+
+        let wallet_a = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+        let wallet_b = MultisigWallet::new(2, vec![pk3, pk1, pk2]).unwrap();
+        let mut wallet_c = MultisigWallet::new(2, vec![pk2, pk3]).unwrap();
+        wallet_c.add_signer(pk1).unwrap();
+
+        let canonical = wallet_a.canonical_key_list();
+        let mut expected = wallet_a.authorized_keys_hex().to_vec();
+        expected.sort();
+        assert_eq!(canonical, expected);
+        assert_ne!(wallet_a.authorized_keys_hex(), wallet_b.authorized_keys_hex());
+
+        for other in [&wallet_b, &wallet_c] {
+            assert_eq!(other.canonical_key_list(), canonical);
+            assert_eq!(other.fingerprint(), wallet_a.fingerprint());
+            assert_eq!(
+                other.aggregated_pubkey().unwrap(),
+                wallet_a.aggregated_pubkey().unwrap()
+            );
+        }
+        assert_eq!(wallet_b.wallet_id(), wallet_a.wallet_id());
     }
 
     #[test]
-    fn test_invalid_threshold() {
+    fn test_from_export_audits_bundle() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        let bundle = wallet.finalize(&tx.id).unwrap();
+        let keys_hex = wallet.authorized_keys_hex().to_vec();
+
+        let (audit, valid) =
+            MultisigWallet::from_export(2, keys_hex.clone(), bundle.clone()).unwrap();
+        assert!(valid);
+        assert_eq!(audit.wallet_id(), wallet.wallet_id());
+        assert_eq!(audit.get_signature_count(&tx.id).unwrap(), 2);
+        assert!(audit.has_enough_signatures(&tx.id).unwrap());
+
+        // pk2's entry swapped for its signature over another transaction
+        let other = Transaction::new("recipient".to_string(), 2000, None);
+        let mut tampered = bundle;
+        let forged = other.sign(&sk2, wallet.wallet_id()).unwrap();
+        let slot = tampered.signatures
+            .iter_mut()
+            .find(|(pk_hex, _)| *pk_hex == hex::encode(pk2.serialize()))
+            .unwrap();
+        slot.1 = hex::encode(forged.serialize_compact());
+
+        let (audit, valid) = MultisigWallet::from_export(2, keys_hex, tampered).unwrap();
+        assert!(!valid);
+        assert_eq!(audit.get_signature_count(&tx.id).unwrap(), 1);
+        assert!(!audit.has_enough_signatures(&tx.id).unwrap());
+    }
+
+    #[test]
+    fn test_duplicate_policy() {
+        use sha2::{Digest, Sha256};
+
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+
+        for policy in [DuplicatePolicy::Reject, DuplicatePolicy::Ignore, DuplicatePolicy::Replace] {
+            let mut wallet = MultisigWallet::new(2, vec![pk1, pk2])
+                .unwrap()
+                .with_duplicate_policy(policy);
+            wallet.propose_transaction(tx.clone()).unwrap();
+            let first = tx.sign(&sk1, wallet.wallet_id()).unwrap();
+            wallet.add_signature(&tx.id, first, &pk1).unwrap();
+
+            // A second valid signature from the same key, with a fresh nonce
+            let digest: [u8; 32] = Sha256::digest(tx.signing_payload(wallet.wallet_id())).into();
+            let mut second = context().sign_ecdsa_with_noncedata(
+                &secp256k1::Message::from_digest(digest),
+                &sk1,
+                &[7u8; 32],
+            );
+            second.normalize_s();
+            assert_ne!(first, second);
+
+            let result = wallet.add_signature(&tx.id, second, &pk1);
+            assert_eq!(wallet.get_signature_count(&tx.id).unwrap(), 1);
+            let stored = wallet.collected_signatures(&tx.id).unwrap()[0].1;
+            match policy {
+                DuplicatePolicy::Reject => {
+                    assert!(matches!(result, Err(MultisigError::DuplicateSignature)));
+                    assert_eq!(stored, first);
+                }
+                DuplicatePolicy::Ignore => {
+                    assert!(result.is_ok());
+                    assert_eq!(stored, first);
+                }
+                DuplicatePolicy::Replace => {
+                    assert!(result.is_ok());
+                    assert_eq!(stored, second);
+                    assert!(matches!(
+                        wallet.events().last(),
+                        Some(WalletEvent::SignatureReplaced { .. })
+                    ));
+                }
+            }
+            assert_eq!(wallet.metrics().signatures_added, 1);
+        }
+    }
+
+    #[test]
+    fn test_batch_follows_duplicate_policy() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+
+        for policy in [DuplicatePolicy::Reject, DuplicatePolicy::Ignore, DuplicatePolicy::Replace] {
+            let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3])
+                .unwrap()
+                .with_duplicate_policy(policy);
+            wallet.propose_transaction(tx.clone()).unwrap();
+            let batch = vec![
+                (tx.sign(&sk1, wallet.wallet_id()).unwrap(), pk1),
+                (tx.sign(&sk2, wallet.wallet_id()).unwrap(), pk2),
+            ];
+            assert_eq!(wallet.add_signatures_batch(&tx.id, batch.clone()).unwrap(), 2);
+
+            // Re-sending the same batch
+            let result = wallet.add_signatures_batch(&tx.id, batch);
+            match policy {
+                DuplicatePolicy::Reject => {
+                    assert!(matches!(result, Err(MultisigError::BatchFailed { added: 0, .. })));
+                }
+                DuplicatePolicy::Ignore | DuplicatePolicy::Replace => {
+                    assert_eq!(result.unwrap(), 0);
+                }
+            }
+            assert_eq!(wallet.get_signature_count(&tx.id).unwrap(), 2);
+            assert_eq!(wallet.metrics().signatures_added, 2);
+            let replaced = wallet.events()
+                .iter()
+                .filter(|event| matches!(event, WalletEvent::SignatureReplaced { .. }))
+                .count();
+            assert_eq!(replaced, if policy == DuplicatePolicy::Replace { 2 } else { 0 });
+        }
+    }
+
+    #[test]
+    fn test_info_detailed_counts() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+
+        let executed = Transaction::new("recipient".to_string(), 1, None);
+        let ready = Transaction::new("recipient".to_string(), 2, None);
+        let partial = Transaction::new("recipient".to_string(), 3, None);
+        let unsigned = Transaction::new("recipient".to_string(), 4, None);
+        for tx in [&executed, &ready, &partial, &unsigned] {
+            wallet.propose_transaction(tx.clone()).unwrap();
+        }
+        for tx in [&executed, &ready] {
+            wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+            wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        }
+        wallet
+            .add_signature(&partial.id, partial.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1)
+            .unwrap();
+        wallet.execute_transaction(&executed.id).unwrap();
+
+        let info = wallet.info_detailed();
+        assert_eq!(info.pending_count, wallet.info().pending_count);
+        assert_eq!(info.pending_count, 4);
+        assert_eq!(info.ready_count, 1);
+        assert_eq!(info.executed_count, 1);
+        assert_eq!(info.total_weight, 2);
+    }
+
+    #[test]
+    fn test_challenge_response() {
+        use crate::crypto::sign_message;
+
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (outsider_sk, outsider) = generate_keypair().unwrap();
+        let wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+
+        let challenge = wallet.challenge();
+        assert!(challenge.contains(&wallet.fingerprint()));
+        assert_ne!(challenge, wallet.challenge());
+
+        let response = sign_message(challenge.as_bytes(), &sk1).unwrap();
+        assert!(wallet.verify_challenge_response(&challenge, response, &pk1).unwrap());
+        assert!(!wallet.verify_challenge_response(&challenge, response, &pk2).unwrap());
+
+        // A response to one challenge does not answer another
+        let other = wallet.challenge();
+        assert!(!wallet.verify_challenge_response(&other, response, &pk1).unwrap());
+
+        let outsider_response = sign_message(challenge.as_bytes(), &outsider_sk).unwrap();
+        assert!(!wallet
+            .verify_challenge_response(&challenge, outsider_response, &outsider)
+            .unwrap());
+
+        // Challenges are bound to the wallet that issued them
+        let other_wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+        let foreign = other_wallet.challenge();
+        let foreign_response = sign_message(foreign.as_bytes(), &sk1).unwrap();
+        assert!(!wallet.verify_challenge_response(&foreign, foreign_response, &pk1).unwrap());
+    }
+
+    #[test]
+    fn test_max_pending_limit() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap().with_max_pending(2);
+
+        let first = Transaction::new("recipient".to_string(), 1, None);
+        let second = Transaction::new("recipient".to_string(), 2, None);
+        let third = Transaction::new("recipient".to_string(), 3, None);
+        wallet.propose_transaction(first.clone()).unwrap();
+        assert!(matches!(
+            wallet.propose_transactions(vec![second.clone(), third.clone()]),
+            Err(MultisigError::PendingLimitReached { limit: 2 })
+        ));
+        wallet.propose_transaction(second).unwrap();
+        assert!(matches!(
+            wallet.propose_transaction(third.clone()),
+            Err(MultisigError::PendingLimitReached { limit: 2 })
+        ));
+
+        // Executing frees a slot; pruning only reclaims storage
+        let sig = first.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&first.id, sig, &pk1).unwrap();
+        wallet.execute_transaction(&first.id).unwrap();
+        wallet.propose_transaction(third).unwrap();
+        assert_eq!(wallet.pending_transactions.len(), 3);
+        assert_eq!(wallet.prune_executed(), 1);
+        assert_eq!(wallet.pending_transactions.len(), 2);
+        assert!(!wallet.pending_transactions.contains_key(&first.id));
+    }
+
+    #[test]
+    fn test_prune_executed_keeps_pending() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+
+        let executed = Transaction::new("recipient".to_string(), 1, None);
+        let signed = Transaction::new("recipient".to_string(), 2, None);
+        let unsigned = Transaction::new("recipient".to_string(), 3, None);
+        for tx in [&executed, &signed, &unsigned] {
+            wallet.propose_transaction(tx.clone()).unwrap();
+        }
+        for tx in [&executed, &signed] {
+            wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        }
+        wallet.execute_transaction(&executed.id).unwrap();
+
+        let before: Vec<&str> =
+            wallet.executed_transactions().iter().map(|tx| tx.id.as_str()).collect();
+        assert_eq!(before, vec![executed.id.as_str()]);
+
+        assert_eq!(wallet.prune_executed(), 1);
+        assert_eq!(wallet.prune_executed(), 0);
+        assert!(wallet.executed_transactions().is_empty());
+        assert_eq!(wallet.pending_transactions().len(), 2);
+        assert!(wallet.has_enough_signatures(&signed.id).unwrap());
+        assert_eq!(wallet.get_signature_count(&unsigned.id).unwrap(), 0);
+
+        // History and replay protection survive the prune
+        assert!(wallet.receipt(&executed.id).is_some());
+        assert!(matches!(
+            wallet.propose_transaction(executed),
+            Err(MultisigError::TransactionAlreadyExecuted)
+        ));
+    }
+
+    #[test]
+    fn test_mixed_signature_schemes() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let keypair2 = Keypair::from_secret_key(context(), &sk2);
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+        wallet.set_signer_scheme(&pk2, SignatureScheme::Schnorr).unwrap();
+        assert_eq!(wallet.signer_scheme(&pk1), SignatureScheme::Ecdsa);
+        assert_eq!(wallet.signer_scheme(&pk2), SignatureScheme::Schnorr);
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        // Each signer must use their own scheme
+        assert!(matches!(
+            wallet.add_schnorr_signature(
+                &tx.id,
+                tx.sign_schnorr(&Keypair::from_secret_key(context(), &sk1), wallet.wallet_id())
+                    .unwrap(),
+                &pk1,
+            ),
+            Err(MultisigError::SignatureSchemeMismatch)
+        ));
+        assert!(matches!(
+            wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2),
+            Err(MultisigError::SignatureSchemeMismatch)
+        ));
+
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+        let schnorr_sig = tx.sign_schnorr(&keypair2, wallet.wallet_id()).unwrap();
+        wallet.add_schnorr_signature(&tx.id, schnorr_sig, &pk2).unwrap();
+        assert!(wallet.has_enough_signatures(&tx.id).unwrap());
+
+        let bundle = wallet.finalize(&tx.id).unwrap();
+        assert!(wallet.verify_signed_transaction(&bundle).unwrap());
+        assert!(wallet.reverify_all(&tx.id).unwrap());
+        assert!(wallet.execute_transaction(&tx.id).is_ok());
+    }
+
+    #[test]
+    fn test_cross_chain_replay_rejected() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let mut chain1 = MultisigWallet::new(1, vec![pk1, pk2]).unwrap().with_chain_id(1);
+        let mut chain2 = MultisigWallet::new(1, vec![pk1, pk2]).unwrap().with_chain_id(2);
+        assert_eq!(chain1.wallet_id(), chain2.wallet_id());
+        assert!(MultisigWallet::new(1, vec![pk1]).unwrap().intent_context().is_none());
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        chain1.propose_transaction(tx.clone()).unwrap();
+        chain2.propose_transaction(tx.clone()).unwrap();
+
+        let context = chain1.intent_context().unwrap();
+        assert_eq!(
+            context,
+            IntentContext { chain_id: 1, wallet_id: chain1.wallet_id().to_string() }
+        );
+        let sig = tx.sign_with_context(&sk1, &context).unwrap();
+        assert!(matches!(
+            chain2.add_signature(&tx.id, sig, &pk1),
+            Err(MultisigError::InvalidSignature { .. })
+        ));
+
+        // A signature without the context does not count either
+        let plain = tx.sign(&sk1, chain1.wallet_id()).unwrap();
+        assert!(matches!(
+            chain1.add_signature(&tx.id, plain, &pk1),
+            Err(MultisigError::InvalidSignature { .. })
+        ));
+
+        chain1.add_signature(&tx.id, sig, &pk1).unwrap();
+        let bundle = chain1.finalize(&tx.id).unwrap();
+        assert!(chain1.verify_signed_transaction(&bundle).unwrap());
+        assert!(!chain2.verify_signed_transaction(&bundle).unwrap());
+        assert!(chain1.execute_transaction(&tx.id).is_ok());
+    }
+
+    #[test]
+    fn test_principal_veto_withdraws_delegate_signature() {
         let (_, pk1) = generate_keypair().unwrap();
-        
-        let wallet = MultisigWallet::new(2, vec![pk1]);
-        assert!(wallet.is_err());
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let (delegate_sk, delegate) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+        wallet.add_delegation(&pk1, delegate).unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        let delegate_sig = tx.sign(&delegate_sk, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx.id, delegate_sig, &delegate).unwrap();
+        wallet.reject_transaction(&tx.id, &pk1).unwrap();
+        assert_eq!(wallet.get_signature_count(&tx.id).unwrap(), 0);
+
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+        assert!(matches!(
+            wallet.execute_transaction(&tx.id),
+            Err(MultisigError::InsufficientSignatures { .. })
+        ));
     }
 
     #[test]
-    fn test_transaction_flow() {
+    fn test_merge_skips_slot_filled_by_delegate() {
         let (sk1, pk1) = generate_keypair().unwrap();
         let (sk2, pk2) = generate_keypair().unwrap();
         let (_, pk3) = generate_keypair().unwrap();
-        
+        let (delegate_sk, delegate) = generate_keypair().unwrap();
+
+        let mut ours = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
+        ours.add_delegation(&pk1, delegate).unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        ours.propose_transaction(tx.clone()).unwrap();
+        let mut theirs = ours.clone();
+
+        let delegate_sig = tx.sign(&delegate_sk, ours.wallet_id()).unwrap();
+        ours.add_signature(&tx.id, delegate_sig, &delegate).unwrap();
+        let mut imported = ours.clone();
+        theirs.add_signature(&tx.id, tx.sign(&sk1, ours.wallet_id()).unwrap(), &pk1).unwrap();
+        theirs.add_signature(&tx.id, tx.sign(&sk2, ours.wallet_id()).unwrap(), &pk2).unwrap();
+
+        assert_eq!(ours.merge_signatures(&tx.id, &theirs).unwrap(), 1);
+        assert_eq!(ours.signed_weight(&ours.pending_transactions[&tx.id]), 2);
+        assert_eq!(imported.import_pending(&theirs.export_pending(&tx.id).unwrap()).unwrap(), 1);
+        assert!(imported.execute_transaction(&tx.id).is_ok());
+    }
+
+    #[test]
+    fn test_remove_mandatory_signer() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
+        let policy = Policy::Required { mandatory: vec![pk3], threshold: 2 };
+        let mut wallet = MultisigWallet::new_with_policy(policy, vec![pk1, pk2, pk3]).unwrap();
+
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk2, wallet.wallet_id()).unwrap(), &pk2).unwrap();
+        assert!(!wallet.has_enough_signatures(&tx.id).unwrap());
+
+        wallet.remove_signer(&pk3).unwrap();
+        assert_eq!(wallet.policy(), Policy::Threshold(2));
+        assert!(wallet.execute_transaction(&tx.id).is_ok());
+    }
+
+    #[test]
+    fn test_failed_propose_and_sign_leaves_wallet_unchanged() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+
+        let expired = Transaction::with_nonce("recipient".to_string(), 1000, None, 7)
+            .with_expiry(current_timestamp() - 10);
+        assert!(matches!(
+            wallet.propose_and_sign(expired, &sk1, &pk1),
+            Err(MultisigError::TransactionExpired)
+        ));
+        assert!(wallet.pending_transactions().is_empty());
+        assert!(wallet.events().is_empty());
+        assert_eq!(wallet.metrics().proposals, 0);
+
+        // The nonce was not used up
+        let retry = Transaction::with_nonce("recipient".to_string(), 1000, None, 7);
+        wallet.propose_and_sign(retry, &sk1, &pk1).unwrap();
+    }
+
+    #[test]
+    fn test_challenge_response_schemes_and_modes() {
+        use crate::crypto::{sign_message, sign_message_eip191, sign_message_schnorr};
+
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (sk2, pk2) = generate_keypair().unwrap();
+        let keypair2 = Keypair::from_secret_key(context(), &sk2);
+
+        // A Schnorr signer answers with a Schnorr signature
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        wallet.set_signer_scheme(&pk2, SignatureScheme::Schnorr).unwrap();
+        let challenge = wallet.challenge();
+        let schnorr_response = sign_message_schnorr(challenge.as_bytes(), &keypair2).unwrap();
+        assert!(wallet.verify_challenge_response(&challenge, schnorr_response, &pk2).unwrap());
+        let ecdsa_response = sign_message(challenge.as_bytes(), &sk2).unwrap();
+        assert!(!wallet.verify_challenge_response(&challenge, ecdsa_response, &pk2).unwrap());
+
+        // EIP-191 wallets expect a personal_sign response
+        let wallet = MultisigWallet::new(1, vec![pk1, pk2])
+            .unwrap()
+            .with_message_mode(MessageMode::Eip191);
+        let challenge = wallet.challenge();
+        let personal = sign_message_eip191(challenge.as_bytes(), &sk1).unwrap();
+        assert!(wallet.verify_challenge_response(&challenge, personal, &pk1).unwrap());
+        let plain = sign_message(challenge.as_bytes(), &sk1).unwrap();
+        assert!(!wallet.verify_challenge_response(&challenge, plain, &pk1).unwrap());
+    }
+
+    #[test]
+    fn test_can_execute_reports_dead_before_insufficient() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (_, pk3) = generate_keypair().unwrap();
         let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
-        
+
+        let expired = Transaction::new("recipient".to_string(), 1000, None).with_expiry(1);
+        wallet.propose_transaction(expired.clone()).unwrap();
+        let pending = wallet.pending_transactions.get_mut(&expired.id).unwrap();
+        let sig = expired.sign(&sk1, &wallet.wallet_id).unwrap();
+        pending
+            .signatures
+            .insert(hex::encode(pk1.serialize()), hex::encode(sig.serialize_compact()));
+        assert_eq!(wallet.can_execute(&expired.id).unwrap(), ExecutionReadiness::Expired);
+
+        // Two vetoes leave the 2-of-3 unreachable and take pk1's signature
+        let vetoed = Transaction::new("recipient".to_string(), 2000, None);
+        wallet.propose_transaction(vetoed.clone()).unwrap();
+        let sig = vetoed.sign(&sk1, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&vetoed.id, sig, &pk1).unwrap();
+        wallet.reject_transaction(&vetoed.id, &pk1).unwrap();
+        wallet.reject_transaction(&vetoed.id, &pk2).unwrap();
+        assert_eq!(wallet.can_execute(&vetoed.id).unwrap(), ExecutionReadiness::Rejected);
+        assert!(matches!(
+            wallet.execute_transaction(&vetoed.id),
+            Err(MultisigError::TransactionRejected)
+        ));
+    }
+
+    #[test]
+    fn test_pending_signers_counts_delegate_signatures() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (delegate_sk, delegate) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+        wallet.add_delegation(&pk1, delegate).unwrap();
         let tx = Transaction::new("recipient".to_string(), 1000, None);
-        let tx_id = tx.id.clone();
-        
         wallet.propose_transaction(tx.clone()).unwrap();
-        
-        // Add first signature
-        let sig1 = tx.sign(&sk1).unwrap();
-        wallet.add_signature(&tx_id, sig1, &pk1).unwrap();
-        
-        assert!(!wallet.has_enough_signatures(&tx_id).unwrap());
-        
-        // Add second signature
-        let sig2 = tx.sign(&sk2).unwrap();
-        wallet.add_signature(&tx_id, sig2, &pk2).unwrap();
-        
-        assert!(wallet.has_enough_signatures(&tx_id).unwrap());
-        
-        // Execute transaction
-        let executed = wallet.execute_transaction(&tx_id);
-        assert!(executed.is_ok());
+
+        let delegate_sig = tx.sign(&delegate_sk, wallet.wallet_id()).unwrap();
+        wallet.add_signature(&tx.id, delegate_sig, &delegate).unwrap();
+        assert_eq!(wallet.pending_signers(&tx.id).unwrap(), vec![pk2]);
+    }
+
+    #[test]
+    fn test_delegate_recoverable_signature() {
+        let (_, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let (delegate_sk, delegate) = generate_keypair().unwrap();
+
+        let mut wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+        wallet.add_delegation(&pk1, delegate).unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+
+        let sig = tx.sign_recoverable(&delegate_sk, wallet.wallet_id()).unwrap();
+        assert_eq!(wallet.add_recoverable_signature(&tx.id, sig).unwrap(), delegate);
+        assert_eq!(wallet.pending_signers(&tx.id).unwrap(), vec![pk2]);
+        assert!(wallet.execute_transaction(&tx.id).is_ok());
+    }
+
+    #[test]
+    fn test_tampered_body_does_not_execute() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        wallet.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+        assert_eq!(wallet.can_execute(&tx.id).unwrap(), ExecutionReadiness::Ready);
+
+        wallet.pending_transactions.get_mut(&tx.id).unwrap().transaction.amount = 999_999;
+        assert!(matches!(wallet.can_execute(&tx.id), Err(MultisigError::TransactionIdMismatch)));
+        assert!(matches!(
+            wallet.execute_transaction(&tx.id),
+            Err(MultisigError::TransactionIdMismatch)
+        ));
+        assert!(!wallet.pending_transactions[&tx.id].executed);
+    }
+
+    #[test]
+    fn test_import_pending_names_bad_key() {
+        let (sk1, pk1) = generate_keypair().unwrap();
+        let (_, pk2) = generate_keypair().unwrap();
+        let mut wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+        let tx = Transaction::new("recipient".to_string(), 1000, None);
+        wallet.propose_transaction(tx.clone()).unwrap();
+        let mut signer = wallet.clone();
+        signer.add_signature(&tx.id, tx.sign(&sk1, wallet.wallet_id()).unwrap(), &pk1).unwrap();
+
+        // A key sorting before every hex key lands at index 0
+        let bytes = STANDARD.decode(signer.export_pending(&tx.id).unwrap()).unwrap();
+        let mut export: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        export["signatures"]["00zz"] = serde_json::Value::String("00".to_string());
+        let blob = STANDARD.encode(serde_json::to_vec(&export).unwrap());
+        assert!(matches!(
+            wallet.import_pending(&blob),
+            Err(MultisigError::InvalidPublicKey { index: Some(0) })
+        ));
     }
 }
@@ -0,0 +1,62 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+
+/// Advisory about a risky but valid wallet configuration
+///
+/// Warnings never block construction; they let tooling point out setups
+/// that give less protection than a multisig wallet is expected to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WalletWarning {
+    /// Any single signer can execute transactions alone
+    ThresholdOfOne,
+    /// Every signer is needed, so losing one key locks the wallet
+    ThresholdEqualsTotal,
+    /// Only one key is authorized
+    SingleSignerRemaining,
+}
+
+impl WalletWarning {
+    /// Stable identifier for matching warnings in tooling
+    pub fn code(&self) -> &'static str {
+        match self {
+            WalletWarning::ThresholdOfOne => "threshold_of_one",
+            WalletWarning::ThresholdEqualsTotal => "threshold_equals_total",
+            WalletWarning::SingleSignerRemaining => "single_signer_remaining",
+        }
+    }
+
+    /// Human-readable explanation of the risk
+    pub fn message(&self) -> &'static str {
+        match self {
+            WalletWarning::ThresholdOfOne => {
+                "threshold is 1, so any single signer can move funds"
+            }
+            WalletWarning::ThresholdEqualsTotal => {
+                "threshold equals the total weight, so one lost key locks the wallet"
+            }
+            WalletWarning::SingleSignerRemaining => "only one signer is authorized",
+        }
+    }
+}
+
+impl fmt::Display for WalletWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_codes_are_stable() {
+        assert_eq!(WalletWarning::ThresholdOfOne.code(), "threshold_of_one");
+        assert_eq!(WalletWarning::ThresholdEqualsTotal.code(), "threshold_equals_total");
+        assert_eq!(WalletWarning::SingleSignerRemaining.code(), "single_signer_remaining");
+        assert_eq!(
+            WalletWarning::SingleSignerRemaining.to_string(),
+            "only one signer is authorized"
+        );
+    }
+}
@@ -5,7 +5,7 @@ fn test_complete_multisig_workflow() {
     // Generate the  keypairs
     let (sk1, pk1) = generate_keypair().unwrap();
     let (sk2, pk2) = generate_keypair().unwrap();
-    let (sk3, pk3) = generate_keypair().unwrap();
+    let (_sk3, pk3) = generate_keypair().unwrap();
     
     // Create 2-of-3 wallet. 
     let mut wallet = MultisigWallet::new(2, vec![pk1, pk2, pk3]).unwrap();
@@ -17,10 +17,10 @@ fn test_complete_multisig_workflow() {
     wallet.propose_transaction(tx.clone()).unwrap();
     
     // Add the signatures.
-    let sig1 = tx.sign(&sk1).unwrap();
+    let sig1 = tx.sign(&sk1, wallet.wallet_id()).unwrap();
     wallet.add_signature(&tx_id, sig1, &pk1).unwrap();
     
-    let sig2 = tx.sign(&sk2).unwrap();
+    let sig2 = tx.sign(&sk2, wallet.wallet_id()).unwrap();
     wallet.add_signature(&tx_id, sig2, &pk2).unwrap();
     
     // Execute
@@ -42,7 +42,7 @@ fn test_insufficient_signatures() {
     wallet.propose_transaction(tx.clone()).unwrap();
     
     // Only one signature
-    let sig1 = tx.sign(&sk1).unwrap();
+    let sig1 = tx.sign(&sk1, wallet.wallet_id()).unwrap();
     wallet.add_signature(&tx_id, sig1, &pk1).unwrap();
     
     // Should fail to execute
@@ -64,8 +64,24 @@ fn test_unauthorized_signer() {
     wallet.propose_transaction(tx.clone()).unwrap();
     
     // Try to sign with unauthorized key
-    let sig = tx.sign(&sk_unauthorized).unwrap();
+    let sig = tx.sign(&sk_unauthorized, wallet.wallet_id()).unwrap();
     let result = wallet.add_signature(&tx_id, sig, &pk_unauthorized);
     
     assert!(result.is_err());
 }
+
+#[test]
+fn test_authorized_key_accessors() {
+    let (_, pk1) = generate_keypair().unwrap();
+    let (_, pk2) = generate_keypair().unwrap();
+    let (_, outsider) = generate_keypair().unwrap();
+
+    let wallet = MultisigWallet::new(1, vec![pk1, pk2]).unwrap();
+    assert!(wallet.is_authorized_key(&pk2));
+    assert!(!wallet.is_authorized_key(&outsider));
+    assert_eq!(wallet.authorized_keys(), vec![pk1, pk2]);
+    assert_eq!(
+        wallet.authorized_keys_hex(),
+        &[hex::encode(pk1.serialize()), hex::encode(pk2.serialize())]
+    );
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "server")]
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use http_body_util::BodyExt;
+use multisig_rs::server::{router, SignRequest};
+use multisig_rs::wallet::TransactionStatus;
+use multisig_rs::{generate_keypair, MultisigWallet, SharedWallet, Transaction};
+use serde::Serialize;
+use tower::ServiceExt;
+
+async fn send(app: &Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    send(app, Request::get(uri).body(Body::empty()).unwrap()).await
+}
+
+async fn post<T: Serialize>(app: &Router, uri: &str, body: &T) -> (StatusCode, serde_json::Value) {
+    let request = Request::post(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_string(body).unwrap()))
+        .unwrap();
+    send(app, request).await
+}
+
+#[tokio::test]
+async fn test_collect_signatures_over_http() {
+    let (sk1, pk1) = generate_keypair().unwrap();
+    let (sk2, pk2) = generate_keypair().unwrap();
+    let wallet = MultisigWallet::new(2, vec![pk1, pk2]).unwrap();
+    let wallet_id = wallet.wallet_id().to_string();
+    let shared = SharedWallet::new(wallet);
+    let app = router(shared.clone());
+
+    let tx = Transaction::new("recipient".to_string(), 1000, None);
+    let (status, body) = post(&app, "/propose", &tx).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let proposed: TransactionStatus = serde_json::from_value(body).unwrap();
+    assert!(!proposed.is_ready);
+
+    for (sk, pk) in [(&sk1, &pk1), (&sk2, &pk2)] {
+        let request = SignRequest {
+            pubkey: hex::encode(pk.serialize()),
+            signature: hex::encode(tx.sign(sk, &wallet_id).unwrap().serialize_compact()),
+        };
+        let uri = format!("/sign/{}", tx.id);
+        let (status, _) = post(&app, &uri, &request).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, body) = get(&app, &format!("/status/{}", tx.id)).await;
+    assert_eq!(status, StatusCode::OK);
+    let ready: TransactionStatus = serde_json::from_value(body).unwrap();
+    assert!(ready.is_ready);
+    assert!(shared.has_enough_signatures(&tx.id).unwrap());
+}
+
+#[tokio::test]
+async fn test_errors_map_to_status_codes() {
+    let (_, pk1) = generate_keypair().unwrap();
+    let (sk_other, pk_other) = generate_keypair().unwrap();
+    let wallet = MultisigWallet::new(1, vec![pk1]).unwrap();
+    let wallet_id = wallet.wallet_id().to_string();
+    let app = router(SharedWallet::new(wallet));
+
+    let (status, body) = get(&app, "/status/missing").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body["error"].as_str().unwrap().contains("missing"));
+
+    let tx = Transaction::new("recipient".to_string(), 1000, None);
+    post(&app, "/propose", &tx).await;
+    let (status, _) = post(&app, "/propose", &tx).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let request = SignRequest {
+        pubkey: hex::encode(pk_other.serialize()),
+        signature: hex::encode(tx.sign(&sk_other, &wallet_id).unwrap().serialize_compact()),
+    };
+    let uri = format!("/sign/{}", tx.id);
+    let (status, _) = post(&app, &uri, &request).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let garbage = SignRequest { pubkey: "zz".to_string(), signature: "00".to_string() };
+    let (status, _) = post(&app, &uri, &garbage).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}